@@ -0,0 +1,197 @@
+/*
+ * C interface to the training modpack menu.
+ *
+ * Ownership:
+ * - tmui_app_new returns an app which belongs to the caller. Free it with tmui_app_free
+ *   exactly once, and don't use it afterwards.
+ * - Strings passed in are NUL-terminated UTF-8. They are only read during the call,
+ *   and can be freed as soon as it returns.
+ * - Strings passed out are written into buffers which belong to the caller, along with
+ *   their terminating NUL. If a buffer is null or too small, nothing is written to it,
+ *   TMUI_STATUS_BUFFER_TOO_SMALL is returned, and the size needed is written to
+ *   required_len. Pass a null buffer to find out the size first.
+ * - Nothing keeps a pointer passed to it after returning, apart from the app itself.
+ * - An app must only be used by one thread at a time.
+ */
+
+#ifndef TRAINING_MOD_TUI_H
+#define TRAINING_MOD_TUI_H
+
+/* Generated by cbindgen from ffi/src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define TMUI_BUTTON_A 0
+
+#define TMUI_BUTTON_B 1
+
+#define TMUI_BUTTON_X 2
+
+#define TMUI_BUTTON_Y 3
+
+#define TMUI_BUTTON_UP 4
+
+#define TMUI_BUTTON_DOWN 5
+
+#define TMUI_BUTTON_LEFT 6
+
+#define TMUI_BUTTON_RIGHT 7
+
+#define TMUI_BUTTON_START 8
+
+#define TMUI_BUTTON_L 9
+
+#define TMUI_BUTTON_R 10
+
+#define TMUI_BUTTON_ZL 11
+
+#define TMUI_BUTTON_ZR 12
+
+#define TMUI_EVENT_PRESS 0
+
+#define TMUI_EVENT_RELEASE 1
+
+#define TMUI_EVENT_HOLD 2
+
+#define TMUI_PAGE_SUBMENU 0
+
+#define TMUI_PAGE_TOGGLE 1
+
+#define TMUI_PAGE_SLIDER 2
+
+#define TMUI_PAGE_CONFIRMATION 3
+
+#define TMUI_PAGE_CLOSE 4
+
+/**
+ * The result of a call
+ */
+typedef enum {
+  TMUI_STATUS_OK = 0,
+  /**
+   * A pointer which must not be null was null
+   */
+  TMUI_STATUS_NULL_POINTER = 1,
+  /**
+   * A string wasn't valid UTF-8
+   */
+  TMUI_STATUS_INVALID_UTF8 = 2,
+  /**
+   * Settings JSON couldn't be read, or didn't match the menu. None of it was applied.
+   */
+  TMUI_STATUS_INVALID_JSON = 3,
+  /**
+   * A button or event number was out of range
+   */
+  TMUI_STATUS_INVALID_ARGUMENT = 4,
+  /**
+   * The buffer was too small, and nothing was written to it. The size needed has been
+   * written to `required_len`.
+   */
+  TMUI_STATUS_BUFFER_TOO_SMALL = 5,
+  /**
+   * Something went wrong inside the menu. The app should be freed.
+   */
+  TMUI_STATUS_PANICKED = 6,
+} TmuiStatus;
+
+/**
+ * An app, along with the definition it was created from. Only ever handled through a
+ * pointer from `tmui_app_new`, which belongs to the caller until passed to `tmui_app_free`.
+ */
+typedef struct TmuiApp TmuiApp;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates an app from a JSON menu definition (see `MenuDefinition`).
+ * Returns null if the definition is null, not UTF-8, or invalid.
+ *
+ * # Safety
+ * `definition_json` must be null or a NUL-terminated string.
+ */
+TmuiApp *tmui_app_new(const char *definition_json);
+
+/**
+ * Frees an app. Does nothing if `app` is null.
+ *
+ * # Safety
+ * `app` must be null or a pointer from `tmui_app_new` which hasn't been freed yet.
+ */
+void tmui_app_free(TmuiApp *app);
+
+/**
+ * Sends a button event, one of the TMUI_EVENT values, for one of the TMUI_BUTTON values
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`.
+ */
+TmuiStatus tmui_app_input(TmuiApp *app, uint32_t button, uint32_t event);
+
+/**
+ * Holds every button which is down for another tick, for hosts which don't send a
+ * TMUI_EVENT_HOLD for each of them
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`.
+ */
+TmuiStatus tmui_app_tick(TmuiApp *app);
+
+/**
+ * The page on display, one of the TMUI_PAGE values, or -1 if `app` is null.
+ * TMUI_PAGE_CLOSE means the player has closed the menu.
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`.
+ */
+int32_t tmui_app_page(const TmuiApp *app);
+
+/**
+ * Writes the settings as NUL-terminated JSON, in the same form `tmui_app_set_settings` takes
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`. `buffer` must be null or
+ * writable for `buffer_len` bytes, and `required_len` must be null or writable.
+ */
+TmuiStatus tmui_app_get_settings(const TmuiApp *app,
+                                 char *buffer,
+                                 size_t buffer_len,
+                                 size_t *required_len);
+
+/**
+ * Replaces the settings listed in a JSON object, keyed by submenu title
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`, and `json` must be null or a
+ * NUL-terminated string.
+ */
+TmuiStatus tmui_app_set_settings(TmuiApp *app, const char *json);
+
+#if defined(TMUI_HAS_TERMINAL)
+/**
+ * Draws the menu at `width` by `height` cells and writes it as NUL-terminated UTF-8 text,
+ * one line per row with each line ending in '\n'. Only built with the terminal UI.
+ *
+ * # Safety
+ * `app` must be null or a live pointer from `tmui_app_new`. `buffer` must be null or
+ * writable for `buffer_len` bytes, and `required_len` must be null or writable.
+ */
+TmuiStatus tmui_app_render(TmuiApp *app,
+                           uint16_t width,
+                           uint16_t height,
+                           char *buffer,
+                           size_t buffer_len,
+                           size_t *required_len);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TRAINING_MOD_TUI_H */
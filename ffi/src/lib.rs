@@ -0,0 +1,269 @@
+//! C interface to the menu, built as a static library for linking into hosts.
+//! The header in `include/` is generated from this file with cbindgen.
+
+use std::ffi::{c_char, CStr};
+use std::mem::ManuallyDrop;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::{ptr, slice};
+
+#[cfg(feature = "has_terminal")]
+use training_mod_tui_2::render_to_text;
+use training_mod_tui_2::{App, AppPage, Button, ButtonEvent, MenuDefinition};
+
+// Buttons, numbered in the order of Button::ALL
+pub const TMUI_BUTTON_A: u32 = 0;
+pub const TMUI_BUTTON_B: u32 = 1;
+pub const TMUI_BUTTON_X: u32 = 2;
+pub const TMUI_BUTTON_Y: u32 = 3;
+pub const TMUI_BUTTON_UP: u32 = 4;
+pub const TMUI_BUTTON_DOWN: u32 = 5;
+pub const TMUI_BUTTON_LEFT: u32 = 6;
+pub const TMUI_BUTTON_RIGHT: u32 = 7;
+pub const TMUI_BUTTON_START: u32 = 8;
+pub const TMUI_BUTTON_L: u32 = 9;
+pub const TMUI_BUTTON_R: u32 = 10;
+pub const TMUI_BUTTON_ZL: u32 = 11;
+pub const TMUI_BUTTON_ZR: u32 = 12;
+
+pub const TMUI_EVENT_PRESS: u32 = 0;
+pub const TMUI_EVENT_RELEASE: u32 = 1;
+pub const TMUI_EVENT_HOLD: u32 = 2;
+
+pub const TMUI_PAGE_SUBMENU: i32 = 0;
+pub const TMUI_PAGE_TOGGLE: i32 = 1;
+pub const TMUI_PAGE_SLIDER: i32 = 2;
+pub const TMUI_PAGE_CONFIRMATION: i32 = 3;
+pub const TMUI_PAGE_CLOSE: i32 = 4;
+
+/// The result of a call
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TmuiStatus {
+    Ok = 0,
+    /// A pointer which must not be null was null
+    NullPointer = 1,
+    /// A string wasn't valid UTF-8
+    InvalidUtf8 = 2,
+    /// Settings JSON couldn't be read, or didn't match the menu. None of it was applied.
+    InvalidJson = 3,
+    /// A button or event number was out of range
+    InvalidArgument = 4,
+    /// The buffer was too small, and nothing was written to it. The size needed has been
+    /// written to `required_len`.
+    BufferTooSmall = 5,
+    /// Something went wrong inside the menu. The app should be freed.
+    Panicked = 6,
+}
+
+/// An app, along with the definition it was created from. Only ever handled through a
+/// pointer from `tmui_app_new`, which belongs to the caller until passed to `tmui_app_free`.
+pub struct TmuiApp {
+    app: ManuallyDrop<App<'static>>,
+    /// From `Box::into_raw`, so that nothing else claims the text the app borrows
+    definition: *mut MenuDefinition,
+}
+
+impl Drop for TmuiApp {
+    fn drop(&mut self) {
+        // The app borrows from the definition, so it has to go first
+        unsafe {
+            ManuallyDrop::drop(&mut self.app);
+            drop(Box::from_raw(self.definition));
+        }
+    }
+}
+
+/// Creates an app from a JSON menu definition (see `MenuDefinition`).
+/// Returns null if the definition is null, not UTF-8, or invalid.
+///
+/// # Safety
+/// `definition_json` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_new(definition_json: *const c_char) -> *mut TmuiApp {
+    let json = match str_arg(definition_json) {
+        Ok(json) => json,
+        Err(_) => return ptr::null_mut(),
+    };
+    let result = catch_unwind(|| {
+        let definition = Box::into_raw(Box::new(MenuDefinition::from_json(json).ok()?));
+        // The definition is only freed once the app borrowing from it has been dropped
+        let app = (*definition).build();
+        Some(Box::new(TmuiApp {
+            app: ManuallyDrop::new(app),
+            definition,
+        }))
+    });
+    match result {
+        Ok(Some(app)) => Box::into_raw(app),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Frees an app. Does nothing if `app` is null.
+///
+/// # Safety
+/// `app` must be null or a pointer from `tmui_app_new` which hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_free(app: *mut TmuiApp) {
+    if !app.is_null() {
+        drop(Box::from_raw(app));
+    }
+}
+
+/// Sends a button event, one of the TMUI_EVENT values, for one of the TMUI_BUTTON values
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_input(app: *mut TmuiApp, button: u32, event: u32) -> TmuiStatus {
+    let Some(app) = app.as_mut() else {
+        return TmuiStatus::NullPointer;
+    };
+    let Some(button) = Button::ALL.get(button as usize).copied() else {
+        return TmuiStatus::InvalidArgument;
+    };
+    let event = match event {
+        TMUI_EVENT_PRESS => ButtonEvent::Press,
+        TMUI_EVENT_RELEASE => ButtonEvent::Release,
+        TMUI_EVENT_HOLD => ButtonEvent::Hold,
+        _ => return TmuiStatus::InvalidArgument,
+    };
+    guard(|| {
+        app.app.handle_input(button, event);
+        TmuiStatus::Ok
+    })
+}
+
+/// Holds every button which is down for another tick, for hosts which don't send a
+/// TMUI_EVENT_HOLD for each of them
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_tick(app: *mut TmuiApp) -> TmuiStatus {
+    let Some(app) = app.as_mut() else {
+        return TmuiStatus::NullPointer;
+    };
+    guard(|| {
+        app.app.tick();
+        TmuiStatus::Ok
+    })
+}
+
+/// The page on display, one of the TMUI_PAGE values, or -1 if `app` is null.
+/// TMUI_PAGE_CLOSE means the player has closed the menu.
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_page(app: *const TmuiApp) -> i32 {
+    match app.as_ref().map(|app| app.app.page) {
+        Some(AppPage::SUBMENU) => TMUI_PAGE_SUBMENU,
+        Some(AppPage::TOGGLE) => TMUI_PAGE_TOGGLE,
+        Some(AppPage::SLIDER) => TMUI_PAGE_SLIDER,
+        Some(AppPage::CONFIRMATION) => TMUI_PAGE_CONFIRMATION,
+        Some(AppPage::CLOSE) => TMUI_PAGE_CLOSE,
+        None => -1,
+    }
+}
+
+/// Writes the settings as NUL-terminated JSON, in the same form `tmui_app_set_settings` takes
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`. `buffer` must be null or
+/// writable for `buffer_len` bytes, and `required_len` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_get_settings(
+    app: *const TmuiApp,
+    buffer: *mut c_char,
+    buffer_len: usize,
+    required_len: *mut usize,
+) -> TmuiStatus {
+    let Some(app) = app.as_ref() else {
+        return TmuiStatus::NullPointer;
+    };
+    guard(|| write_str(&app.app.to_json(), buffer, buffer_len, required_len))
+}
+
+/// Replaces the settings listed in a JSON object, keyed by submenu title
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`, and `json` must be null or a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_set_settings(
+    app: *mut TmuiApp,
+    json: *const c_char,
+) -> TmuiStatus {
+    let Some(app) = app.as_mut() else {
+        return TmuiStatus::NullPointer;
+    };
+    let json = match str_arg(json) {
+        Ok(json) => json,
+        Err(status) => return status,
+    };
+    match catch_unwind(AssertUnwindSafe(|| app.app.update_from_json(json))) {
+        Ok(Ok(())) => TmuiStatus::Ok,
+        Ok(Err(_)) => TmuiStatus::InvalidJson,
+        Err(_) => TmuiStatus::Panicked,
+    }
+}
+
+/// Draws the menu at `width` by `height` cells and writes it as NUL-terminated UTF-8 text,
+/// one line per row with each line ending in '\n'. Only built with the terminal UI.
+///
+/// # Safety
+/// `app` must be null or a live pointer from `tmui_app_new`. `buffer` must be null or
+/// writable for `buffer_len` bytes, and `required_len` must be null or writable.
+#[cfg(feature = "has_terminal")]
+#[no_mangle]
+pub unsafe extern "C" fn tmui_app_render(
+    app: *mut TmuiApp,
+    width: u16,
+    height: u16,
+    buffer: *mut c_char,
+    buffer_len: usize,
+    required_len: *mut usize,
+) -> TmuiStatus {
+    let Some(app) = app.as_ref() else {
+        return TmuiStatus::NullPointer;
+    };
+    guard(|| {
+        let text = render_to_text(&app.app, width, height);
+        write_str(&text, buffer, buffer_len, required_len)
+    })
+}
+
+/// Runs `f`, turning a panic into a status rather than unwinding into the caller
+fn guard(f: impl FnOnce() -> TmuiStatus) -> TmuiStatus {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(TmuiStatus::Panicked)
+}
+
+unsafe fn str_arg<'s>(text: *const c_char) -> Result<&'s str, TmuiStatus> {
+    if text.is_null() {
+        return Err(TmuiStatus::NullPointer);
+    }
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| TmuiStatus::InvalidUtf8)
+}
+
+/// Copies `text` and a terminating NUL into the caller's buffer, if it fits
+unsafe fn write_str(
+    text: &str,
+    buffer: *mut c_char,
+    buffer_len: usize,
+    required_len: *mut usize,
+) -> TmuiStatus {
+    let required = text.len() + 1;
+    if !required_len.is_null() {
+        *required_len = required;
+    }
+    if buffer.is_null() || buffer_len < required {
+        return TmuiStatus::BufferTooSmall;
+    }
+    let out = slice::from_raw_parts_mut(buffer as *mut u8, required);
+    out[..text.len()].copy_from_slice(text.as_bytes());
+    out[text.len()] = 0;
+    TmuiStatus::Ok
+}
@@ -0,0 +1,208 @@
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use training_mod_tui_2::Button;
+use training_mod_tui_2_ffi::*;
+
+const DEFINITION: &str = r#"{"tabs": [
+    {"id": "tab", "title": "Tab", "submenus": [
+        {"id": "toggle", "title": "Toggle", "type": "ToggleSingle",
+         "toggles": [{"title": "On", "value": 1}, {"title": "Off"}]},
+        {"id": "slider", "title": "Slider", "type": "Slider", "slider": {"min": 0, "max": 150}}
+    ]}
+]}"#;
+
+fn new_app() -> *mut TmuiApp {
+    let definition = CString::new(DEFINITION).unwrap();
+    let app = unsafe { tmui_app_new(definition.as_ptr()) };
+    assert!(!app.is_null());
+    app
+}
+
+/// Reads the settings through a buffer of exactly the size asked for
+fn get_settings(app: *const TmuiApp) -> String {
+    let mut len = 0;
+    let status = unsafe { tmui_app_get_settings(app, ptr::null_mut(), 0, &mut len) };
+    assert_eq!(status, TmuiStatus::BufferTooSmall);
+    let mut buffer: Vec<c_char> = vec![0; len];
+    let status = unsafe { tmui_app_get_settings(app, buffer.as_mut_ptr(), len, &mut len) };
+    assert_eq!(status, TmuiStatus::Ok);
+    let text = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+    text.to_str().unwrap().to_string()
+}
+
+#[test]
+fn ffi_new_and_free() {
+    unsafe {
+        assert!(tmui_app_new(ptr::null()).is_null());
+        let invalid = CString::new("{\"tabs\": []}").unwrap();
+        assert!(tmui_app_new(invalid.as_ptr()).is_null());
+        let not_utf8 = CString::new(vec![0xff, 0xfe]).unwrap();
+        assert!(tmui_app_new(not_utf8.as_ptr()).is_null());
+
+        // The definition is copied, so it can be freed straight away
+        let definition = CString::new(DEFINITION).unwrap();
+        let app = tmui_app_new(definition.as_ptr());
+        drop(definition);
+        assert_eq!(tmui_app_page(app), TMUI_PAGE_SUBMENU);
+        tmui_app_free(app);
+        tmui_app_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn ffi_input() {
+    // The button numbers follow Button::ALL
+    let numbers = [
+        TMUI_BUTTON_A,
+        TMUI_BUTTON_B,
+        TMUI_BUTTON_X,
+        TMUI_BUTTON_Y,
+        TMUI_BUTTON_UP,
+        TMUI_BUTTON_DOWN,
+        TMUI_BUTTON_LEFT,
+        TMUI_BUTTON_RIGHT,
+        TMUI_BUTTON_START,
+        TMUI_BUTTON_L,
+        TMUI_BUTTON_R,
+        TMUI_BUTTON_ZL,
+        TMUI_BUTTON_ZR,
+    ];
+    for (idx, number) in numbers.iter().enumerate() {
+        assert_eq!(*number as usize, idx);
+    }
+    assert_eq!(numbers.len(), Button::ALL.len());
+
+    let app = new_app();
+    unsafe {
+        assert_eq!(
+            tmui_app_input(app, TMUI_BUTTON_A, TMUI_EVENT_PRESS),
+            TmuiStatus::Ok
+        );
+        assert_eq!(tmui_app_page(app), TMUI_PAGE_TOGGLE);
+        assert_eq!(
+            tmui_app_input(app, TMUI_BUTTON_A, TMUI_EVENT_RELEASE),
+            TmuiStatus::Ok
+        );
+        assert_eq!(tmui_app_tick(app), TmuiStatus::Ok);
+        assert_eq!(
+            tmui_app_input(app, 13, TMUI_EVENT_PRESS),
+            TmuiStatus::InvalidArgument
+        );
+        assert_eq!(
+            tmui_app_input(app, TMUI_BUTTON_A, 3),
+            TmuiStatus::InvalidArgument
+        );
+        assert_eq!(
+            tmui_app_input(ptr::null_mut(), TMUI_BUTTON_A, TMUI_EVENT_PRESS),
+            TmuiStatus::NullPointer
+        );
+        assert_eq!(tmui_app_tick(ptr::null_mut()), TmuiStatus::NullPointer);
+        assert_eq!(tmui_app_page(ptr::null()), -1);
+
+        tmui_app_input(app, TMUI_BUTTON_START, TMUI_EVENT_PRESS);
+        assert_eq!(tmui_app_page(app), TMUI_PAGE_CLOSE);
+        tmui_app_free(app);
+    }
+}
+
+#[test]
+fn ffi_settings() {
+    let app = new_app();
+    assert_eq!(get_settings(app), "{\"Toggle\":[1,0],\"Slider\":[0,150]}");
+
+    unsafe {
+        // A buffer one byte short is left untouched
+        let mut len = 0;
+        let mut buffer: Vec<c_char> = vec![7; 33];
+        assert_eq!(
+            tmui_app_get_settings(app, buffer.as_mut_ptr(), buffer.len(), &mut len),
+            TmuiStatus::BufferTooSmall
+        );
+        assert_eq!(len, 34);
+        assert!(buffer.iter().all(|c| *c == 7));
+        assert_eq!(
+            tmui_app_get_settings(app, buffer.as_mut_ptr(), buffer.len(), ptr::null_mut()),
+            TmuiStatus::BufferTooSmall
+        );
+
+        let json = CString::new("{\"Slider\":[40,100]}").unwrap();
+        assert_eq!(tmui_app_set_settings(app, json.as_ptr()), TmuiStatus::Ok);
+        assert_eq!(get_settings(app), "{\"Toggle\":[1,0],\"Slider\":[40,100]}");
+
+        // Nothing is applied if any value doesn't fit, even ones before it
+        for invalid in [
+            "[",
+            "[1, 2]",
+            "{\"Slider\":[1,2,3]}",
+            "{\"Toggle\":[0,1],\"Slider\":[100,40]}",
+        ] {
+            let json = CString::new(invalid).unwrap();
+            assert_eq!(
+                tmui_app_set_settings(app, json.as_ptr()),
+                TmuiStatus::InvalidJson
+            );
+        }
+        assert_eq!(get_settings(app), "{\"Toggle\":[1,0],\"Slider\":[40,100]}");
+        assert_eq!(
+            tmui_app_set_settings(app, ptr::null()),
+            TmuiStatus::NullPointer
+        );
+        assert_eq!(
+            tmui_app_get_settings(ptr::null(), buffer.as_mut_ptr(), 33, &mut len),
+            TmuiStatus::NullPointer
+        );
+        tmui_app_free(app);
+    }
+}
+
+#[test]
+#[cfg(feature = "has_terminal")]
+fn ffi_render() {
+    let app = new_app();
+    unsafe {
+        let mut len = 0;
+        assert_eq!(
+            tmui_app_render(app, 80, 24, ptr::null_mut(), 0, &mut len),
+            TmuiStatus::BufferTooSmall
+        );
+        let mut buffer: Vec<c_char> = vec![0; len];
+        assert_eq!(
+            tmui_app_render(app, 80, 24, buffer.as_mut_ptr(), len, &mut len),
+            TmuiStatus::Ok
+        );
+        let text = CStr::from_ptr(buffer.as_ptr()).to_str().unwrap();
+        assert_eq!(text.len() + 1, len);
+        assert_eq!(text.lines().count(), 24);
+        assert!(text.contains("Toggle"));
+        assert!(text.contains("Slider"));
+        tmui_app_free(app);
+    }
+}
+
+#[test]
+fn ffi_header_is_up_to_date() {
+    // Every function and constant needs to be in the header after regenerating it
+    let header = include_str!("../include/training_mod_tui.h");
+    let source = include_str!("../src/lib.rs");
+    for line in source.lines() {
+        if let Some(rest) = line.strip_prefix("pub unsafe extern \"C\" fn ") {
+            let name = &rest[..rest.find('(').unwrap()];
+            assert!(
+                header.contains(&format!("{}(", name)),
+                "{} is missing",
+                name
+            );
+        }
+        if let Some(rest) = line.strip_prefix("pub const ") {
+            let name = &rest[..rest.find(':').unwrap()];
+            let value = rest[rest.find('=').unwrap() + 1..]
+                .trim()
+                .trim_end_matches(';');
+            assert!(
+                header.contains(&format!("#define {} {}\n", name, value)),
+                "{} is missing",
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use alloc::{boxed::Box, string::String};
+
+use crate::App;
+
+pub const ACTION_RESET_DEFAULTS: &str = "reset_defaults";
+pub const ACTION_SAVE_DEFAULTS: &str = "save_defaults";
+/// Themes belong to the renderer, so frontends register this one themselves
+pub const ACTION_CYCLE_THEME: &str = "cycle_theme";
+
+/// Callback invoked when an Action submenu is activated.
+/// The returned message, if any, is displayed to the player.
+pub type ActionCallback<'a> = Box<dyn FnMut(&mut App<'a>) -> Option<String> + 'a>;
+
+/// An action registered on the App, keyed by the id of the submenu that invokes it.
+/// If `confirmation` is set, the player is asked to confirm with that prompt first.
+pub struct MenuAction<'a> {
+    pub confirmation: Option<&'a str>,
+    pub callback: ActionCallback<'a>,
+}
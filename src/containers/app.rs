@@ -1,10 +1,23 @@
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
 use serde::ser::{SerializeMap, Serializer};
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{InputControl, StatefulList, SubMenu, SubMenuType, Tab};
+use crate::{
+    ActionCallback, AutoRepeat, Button, DirtySettings, EntryKey, EventCallback, InputControl,
+    InputSource, MenuAction, MenuEvent, SettingPath, StatefulList, StatefulTable, SubMenu,
+    SubMenuType, Tab, ACTION_RESET_DEFAULTS, ACTION_SAVE_DEFAULTS,
+};
 
-#[derive(PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AppPage {
     SUBMENU,
     TOGGLE,
@@ -24,23 +37,98 @@ pub enum AppPage {
 //       │
 //       │  OR
 //       │
-//       └─ Option<Slider>
+//       ├─ Option<Slider>
+//       │
+//       │  OR
+//       │
+//       └─ StatefulTable<Submenu> (Nested, which repeats the Submenu level)
+//
+// Opening a Nested submenu pushes a level onto the page stack, tracked by `submenu_depth`.
+// The selection of each table along the way determines which submenus are open.
 
 pub struct App<'a> {
     pub tabs: StatefulList<Tab<'a>>,
     pub page: AppPage,
     pub serialized_settings: String,
     pub serialized_default_settings: String,
+    pub actions: BTreeMap<&'a str, MenuAction<'a>>,
+    pub pending_action: Option<&'a str>,
+    /// The page to go back to once the pending action is confirmed or cancelled
+    pub confirmation_return: AppPage,
+    pub action_message: Option<String>,
+    pub submenu_depth: usize,
+    /// Whether the help overlay is shown on top of the current page
+    pub help_open: bool,
+    /// Number of lines the help overlay is scrolled down by
+    pub help_scroll: u16,
+    /// Decides whether button hints show controller buttons or keyboard keys
+    pub input_source: InputSource,
+    /// Buttons which are down, with the number of Hold events since each was pressed
+    pub held_buttons: BTreeMap<Button, u32>,
+    /// How held directions repeat
+    pub auto_repeat: AutoRepeat,
+    /// Receives change events as they happen. Without one, events queue up for `drain_events`.
+    pub event_callback: Option<EventCallback<'a>>,
+    pub(crate) event_queue: Vec<MenuEvent>,
+    /// Every setting's value by where it is, as of when changes were last checked for
+    pub(crate) settings_snapshot: Option<BTreeMap<SettingPath<'a>, Value>>,
+    /// Settings which may have changed since they were last checked
+    pub(crate) dirty_settings: DirtySettings<'a>,
+    /// Whether MenuClosed has been sent since the menu last closed
+    pub(crate) close_reported: bool,
+}
+
+impl<'a> Default for App<'a> {
+    fn default() -> App<'a> {
+        App::new()
+    }
 }
 
 impl<'a> App<'a> {
     pub fn new() -> App<'a> {
-        App {
+        let mut app = App {
             tabs: StatefulList::new(),
             page: AppPage::SUBMENU,
             serialized_settings: String::new(),
             serialized_default_settings: String::new(),
-        }
+            actions: BTreeMap::new(),
+            pending_action: None,
+            confirmation_return: AppPage::SUBMENU,
+            action_message: None,
+            submenu_depth: 0,
+            help_open: false,
+            help_scroll: 0,
+            input_source: InputSource::Controller,
+            held_buttons: BTreeMap::new(),
+            auto_repeat: AutoRepeat::default(),
+            event_callback: None,
+            event_queue: vec![],
+            settings_snapshot: None,
+            dirty_settings: DirtySettings::Clean,
+            close_reported: false,
+        };
+        app.register_action(
+            ACTION_RESET_DEFAULTS,
+            Some("Reset all settings to their defaults?"),
+            Box::new(|app: &mut App<'a>| {
+                if app.serialized_default_settings.is_empty() {
+                    return Some("No defaults have been saved yet".to_string());
+                }
+                match app.load_defaults() {
+                    Ok(()) => Some("Settings reset to defaults".to_string()),
+                    Err(err) => Some(format!("Could not reset the settings: {}", err)),
+                }
+            }),
+        );
+        app.register_action(
+            ACTION_SAVE_DEFAULTS,
+            Some("Save the current settings as defaults?"),
+            Box::new(|app: &mut App<'a>| {
+                app.save_default_settings();
+                Some("Current settings saved as defaults".to_string())
+            }),
+        );
+        app
     }
 
     pub fn to_json(&self) -> String {
@@ -53,38 +141,266 @@ impl<'a> App<'a> {
 
     pub fn save_default_settings(&mut self) {
         self.serialized_default_settings = self.to_json();
+        self.emit(MenuEvent::DefaultsSaved);
     }
 
-    pub fn load_defaults(&mut self) {
+    pub fn load_defaults(&mut self) -> Result<(), SettingsError> {
         // TODO!() is there a way to do this without cloning?
         let json = self.serialized_default_settings.clone();
-        self.update_from_json(&json);
+        self.update_from_json(&json)
     }
 
-    pub fn update_from_json(&mut self, json: &str) {
-        let all_settings: HashMap<String, Vec<u8>> =
-            serde_json::from_str(json).expect("Could not parse the json!");
+    /// Loads the settings listed in a JSON object, keyed by submenu title.
+    /// Every value is checked first, so nothing changes if any of them don't fit their submenus.
+    pub fn update_from_json(&mut self, json: &str) -> Result<(), SettingsError> {
+        let all_settings: BTreeMap<String, Value> = serde_json::from_str(json)?;
+        for tab in self.tabs.iter() {
+            for submenu in tab.submenus.items.iter().flatten().flatten() {
+                if let Some(val) = all_settings.get(submenu.title) {
+                    submenu.check_value(val)?;
+                }
+            }
+        }
+        // Changes made before the load are reported separately from the load itself
+        self.watch_settings();
+        self.check_for_changes();
         for tab in self.tabs.iter_mut() {
-            for submenu_opt in tab.submenus.iter_mut() {
-                if let Some(submenu) = submenu_opt {
-                    if let Some(val) = all_settings.get(submenu.title) {
-                        submenu.update_from_vec(val.clone());
-                    }
+            for submenu in tab.submenus.iter_mut().flatten() {
+                if let Some(val) = all_settings.get(submenu.title) {
+                    submenu.update_from_value(val);
                 }
             }
         }
+        self.mark_settings_changed();
+        self.check_for_changes();
+        self.emit(MenuEvent::PresetLoaded);
+        Ok(())
+    }
+
+    /// Registers a callback to run when the Action submenu with the given id is activated.
+    /// Replaces any action previously registered under that id.
+    pub fn register_action(
+        &mut self,
+        id: &'a str,
+        confirmation: Option<&'a str>,
+        callback: ActionCallback<'a>,
+    ) {
+        self.actions.insert(
+            id,
+            MenuAction {
+                confirmation,
+                callback,
+            },
+        );
+    }
+
+    /// Runs the action immediately, or asks for confirmation first if the action requires it
+    pub fn activate_action(&mut self, id: &'a str) {
+        let needs_confirmation = match self.actions.get(id) {
+            Some(action) => action.confirmation.is_some(),
+            None => {
+                self.action_message = Some(format!("No action registered for {}", id));
+                return;
+            }
+        };
+        if needs_confirmation {
+            self.pending_action = Some(id);
+            if self.page != AppPage::CONFIRMATION {
+                self.confirmation_return = self.page;
+            }
+            self.page = AppPage::CONFIRMATION;
+        } else {
+            self.run_action(id);
+        }
+    }
+
+    /// Runs the action without asking for confirmation
+    pub fn run_action(&mut self, id: &'a str) {
+        // Temporarily take the action out of the map so the callback can borrow the App
+        if let Some(mut action) = self.actions.remove(id) {
+            self.action_message = (action.callback)(self);
+            self.actions.insert(id, action);
+            // The callback may have changed any of the settings
+            self.mark_settings_changed();
+        }
+    }
+
+    /// The prompt for the action awaiting confirmation, if any
+    pub fn pending_confirmation(&self) -> Option<&'a str> {
+        self.pending_action
+            .and_then(|id| self.actions.get(id))
+            .and_then(|action| action.confirmation)
     }
 
     pub fn selected_tab(&mut self) -> &mut Tab<'a> {
         self.tabs.get_selected().expect("No tab selected!")
     }
 
+    /// The grid of submenus on display, descending into each opened Nested submenu
+    pub fn selected_submenu_table(&mut self) -> &mut StatefulTable<SubMenu<'a>> {
+        let depth = self.submenu_depth;
+        let mut table = &mut self.selected_tab().submenus;
+        for _ in 0..depth {
+            table = table
+                .get_selected()
+                .expect("No submenu selected!")
+                .submenus
+                .as_mut()
+                .expect("Not a Nested submenu!");
+        }
+        table
+    }
+
     pub fn selected_submenu(&mut self) -> &mut SubMenu<'a> {
-        self.selected_tab()
-            .submenus
+        self.selected_submenu_table()
             .get_selected()
             .expect("No submenu selected!")
     }
+
+    /// The selected tab, for reading
+    pub fn current_tab(&self) -> &Tab<'a> {
+        self.tabs.selected_item().expect("No tab selected!")
+    }
+
+    /// The grid of submenus on display, for reading
+    pub fn current_submenu_table(&self) -> &StatefulTable<SubMenu<'a>> {
+        let mut table = &self.current_tab().submenus;
+        for _ in 0..self.submenu_depth {
+            table = table
+                .selected_item()
+                .expect("No submenu selected!")
+                .submenus
+                .as_ref()
+                .expect("Not a Nested submenu!");
+        }
+        table
+    }
+
+    /// The selected submenu, about to be changed by input.
+    /// Its setting is compared against its old value when changes are next checked for.
+    pub(crate) fn edit_selected_submenu(&mut self) -> &mut SubMenu<'a> {
+        let path = self.selected_setting_path();
+        self.mark_setting_changed(path);
+        self.selected_submenu()
+    }
+
+    /// Where the selected submenu is, for matching its setting up between checks for changes
+    fn selected_setting_path(&self) -> SettingPath<'a> {
+        let mut ids = vec![];
+        let mut table = &self.current_tab().submenus;
+        for _ in 0..self.submenu_depth {
+            let submenu = table.selected_item().expect("No submenu selected!");
+            ids.push(submenu.id);
+            table = submenu.submenus.as_ref().expect("Not a Nested submenu!");
+        }
+        ids.push(self.current_submenu().id);
+        SettingPath {
+            tab: self.tabs.state.selected().unwrap_or(0),
+            ids,
+        }
+    }
+
+    /// The selected submenu, for reading
+    pub fn current_submenu(&self) -> &SubMenu<'a> {
+        self.current_submenu_table()
+            .selected_item()
+            .expect("No submenu selected!")
+    }
+
+    /// Reflows the grid on display into `cols` columns, up to the number it was created with,
+    /// keeping the same item selected. Frontends call this with the number of columns which
+    /// fit on screen, e.g. `UiState::columns`. Returns whether the grid changed shape.
+    pub fn fit_columns(&mut self, cols: usize) -> bool {
+        match self.page {
+            _ if cols == 0 => false,
+            AppPage::SUBMENU => self.selected_submenu_table().reflow(cols),
+            AppPage::TOGGLE => self.selected_submenu().toggles.reflow(cols),
+            _ => false,
+        }
+    }
+
+    /// Titles of the selected tab and each opened Nested submenu, outermost first
+    pub fn breadcrumbs(&self) -> Vec<&'a str> {
+        let tab = self.current_tab();
+        let mut crumbs = vec![tab.title];
+        let mut table = &tab.submenus;
+        for _ in 0..self.submenu_depth {
+            let submenu = table.selected_item().expect("No submenu selected!");
+            crumbs.push(submenu.title);
+            table = submenu.submenus.as_ref().expect("Not a Nested submenu!");
+        }
+        crumbs
+    }
+
+    /// Title and help text of the focused toggle, or of the focused submenu if
+    /// the toggle doesn't have any help of its own
+    pub fn focused_help(&self) -> Option<(&'a str, &'a str)> {
+        match self.page {
+            AppPage::SUBMENU | AppPage::SLIDER => {
+                let submenu = self.current_submenu();
+                Some((submenu.title, submenu.help_text))
+            }
+            AppPage::TOGGLE => {
+                let submenu = self.current_submenu();
+                let (title, help_text) = (submenu.title, submenu.help_text);
+                match submenu.toggles.selected_item() {
+                    Some(toggle) if !toggle.help_text.is_empty() => {
+                        Some((toggle.title, toggle.help_text))
+                    }
+                    _ => Some((title, help_text)),
+                }
+            }
+            AppPage::CONFIRMATION | AppPage::CLOSE => None,
+        }
+    }
+
+    /// Shows the help overlay, if there is anything focused to show help for
+    pub fn open_help(&mut self) {
+        self.help_open = self.focused_help().is_some();
+        self.help_scroll = 0;
+    }
+
+    pub fn close_help(&mut self) {
+        self.help_open = false;
+        self.help_scroll = 0;
+    }
+
+    /// Whether a value is being entered for the open slider
+    pub fn numeric_entry_active(&self) -> bool {
+        self.page == AppPage::SLIDER
+            && self
+                .current_submenu()
+                .slider
+                .as_ref()
+                .is_some_and(|slider| slider.is_entering())
+    }
+
+    /// Types a key into the open slider's numeric entry.
+    /// Returns false if no value is being entered, so the key can be handled elsewhere.
+    pub fn on_entry_key(&mut self, key: EntryKey) -> bool {
+        if !self.numeric_entry_active() {
+            return false;
+        }
+        if let Some(entry) = self
+            .selected_submenu()
+            .slider
+            .as_mut()
+            .and_then(|slider| slider.entry_mut())
+        {
+            entry.type_key(key);
+        }
+        true
+    }
+
+    fn select_tab(&mut self, next: bool) {
+        if next {
+            self.tabs.next();
+        } else {
+            self.tabs.previous();
+        }
+        // Nested submenus belong to the tab we just left
+        self.submenu_depth = 0;
+    }
 }
 
 impl<'a> Serialize for App<'a> {
@@ -94,10 +410,11 @@ impl<'a> Serialize for App<'a> {
     {
         // Serializes as a mapping between submenu titles and values
         // Need to iterate through tabs to avoid making a list of mappings
-        let len: usize = self.tabs.iter().map(|tab| tab.len()).sum();
+        // Action submenus don't hold a value, so they are skipped
+        let len: usize = self.tabs.iter().map(|tab| tab.serializable_len()).sum();
         let mut map = serializer.serialize_map(Some(len))?;
         for tab in self.tabs.iter() {
-            for submenu in tab.submenus.iter() {
+            for submenu in tab.submenus.iter().filter(|s| s.is_serializable()) {
                 map.serialize_entry(submenu.title, submenu)?;
             }
         }
@@ -107,47 +424,54 @@ impl<'a> Serialize for App<'a> {
 
 impl<'a> InputControl for App<'a> {
     fn on_a(&mut self) {
+        if self.help_open {
+            return;
+        }
         match self.page {
             AppPage::SUBMENU => {
-                let tab = self.tabs.get_selected().expect("No tab selected!");
-                let submenu_type = tab
-                    .submenus
-                    .get_selected()
-                    .expect("No submenu selected!")
-                    .submenu_type;
-                self.page = match submenu_type {
-                    SubMenuType::ToggleSingle => AppPage::TOGGLE,
-                    SubMenuType::ToggleMultiple => AppPage::TOGGLE,
-                    SubMenuType::Slider => AppPage::SLIDER,
-                    SubMenuType::None => AppPage::SUBMENU,
+                let submenu = self.selected_submenu();
+                let submenu_type = submenu.submenu_type;
+                let submenu_id = submenu.id;
+                match submenu_type {
+                    SubMenuType::ToggleSingle => self.page = AppPage::TOGGLE,
+                    SubMenuType::ToggleMultiple => self.page = AppPage::TOGGLE,
+                    SubMenuType::Slider => self.page = AppPage::SLIDER,
+                    SubMenuType::Nested => {
+                        if submenu.has_nested() {
+                            self.submenu_depth += 1;
+                        }
+                    }
+                    SubMenuType::Action => self.activate_action(submenu_id),
+                    SubMenuType::None => {}
                 };
-                tab.on_a()
+                self.selected_tab().on_a()
+            }
+            AppPage::TOGGLE => self.edit_selected_submenu().on_a(),
+            AppPage::SLIDER => self.edit_selected_submenu().on_a(),
+            AppPage::CONFIRMATION => {
+                // Run the confirmed action and return to the page it was started from
+                self.page = self.confirmation_return;
+                if let Some(id) = self.pending_action.take() {
+                    self.run_action(id);
+                }
             }
-            AppPage::TOGGLE => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_a(),
-            AppPage::SLIDER => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_a(),
-            AppPage::CONFIRMATION => {}
             AppPage::CLOSE => {}
         }
     }
     fn on_b(&mut self) {
+        if self.help_open {
+            self.close_help();
+            return;
+        }
         match self.page {
             AppPage::SUBMENU => {
-                // Exit the app
-                self.page = AppPage::CLOSE;
+                if self.submenu_depth > 0 {
+                    // Walk back up one level of Nested submenus
+                    self.submenu_depth -= 1;
+                } else {
+                    // Exit the app
+                    self.page = AppPage::CLOSE;
+                }
             }
             AppPage::TOGGLE => {
                 // Return to the list of submenus
@@ -163,120 +487,86 @@ impl<'a> InputControl for App<'a> {
                 if !slider.is_handle_selected() {
                     self.page = AppPage::SUBMENU;
                 } else {
-                    self.selected_submenu().on_b();
+                    self.edit_selected_submenu().on_b();
                 }
             }
             AppPage::CONFIRMATION => {
-                // Return to the list of submenus
-                self.page = AppPage::SUBMENU;
+                // Cancel the pending action and return to the page it was started from
+                self.pending_action = None;
+                self.page = self.confirmation_return;
             }
             AppPage::CLOSE => {}
         }
     }
     fn on_x(&mut self) {
-        self.save_default_settings();
+        if self.help_open {
+            return;
+        }
+        match self.page {
+            // Start entering a value for the selected slider handle
+            AppPage::SLIDER => self.edit_selected_submenu().on_x(),
+            _ => self.save_default_settings(),
+        }
+    }
+    fn on_y(&mut self) {
+        // Toggle the help overlay for whatever is focused
+        if self.help_open {
+            self.close_help();
+        } else {
+            self.open_help();
+        }
     }
-    fn on_y(&mut self) {}
     fn on_up(&mut self) {
+        if self.help_open {
+            self.help_scroll = self.help_scroll.saturating_sub(1);
+            return;
+        }
+        self.action_message = None;
         match self.page {
-            AppPage::SUBMENU => self.tabs.get_selected().expect("No tab selected!").on_up(),
-            AppPage::TOGGLE => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_up(),
-            AppPage::SLIDER => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_up(),
+            AppPage::SUBMENU => self.selected_submenu_table().prev_row_checked(),
+            AppPage::TOGGLE => self.edit_selected_submenu().on_up(),
+            AppPage::SLIDER => self.edit_selected_submenu().on_up(),
             AppPage::CONFIRMATION => {}
             AppPage::CLOSE => {}
         }
     }
     fn on_down(&mut self) {
+        if self.help_open {
+            // The renderer stops this from scrolling past the end of the text
+            self.help_scroll = self.help_scroll.saturating_add(1);
+            return;
+        }
+        self.action_message = None;
         match self.page {
-            AppPage::SUBMENU => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .on_down(),
-            AppPage::TOGGLE => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_down(),
-            AppPage::SLIDER => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_down(),
+            AppPage::SUBMENU => self.selected_submenu_table().next_row_checked(),
+            AppPage::TOGGLE => self.edit_selected_submenu().on_down(),
+            AppPage::SLIDER => self.edit_selected_submenu().on_down(),
             AppPage::CONFIRMATION => {}
             AppPage::CLOSE => {}
         }
     }
     fn on_left(&mut self) {
+        if self.help_open {
+            return;
+        }
+        self.action_message = None;
         match self.page {
-            AppPage::SUBMENU => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .on_left(),
-            AppPage::TOGGLE => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_left(),
-            AppPage::SLIDER => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_left(),
+            AppPage::SUBMENU => self.selected_submenu_table().prev_col_checked(),
+            AppPage::TOGGLE => self.edit_selected_submenu().on_left(),
+            AppPage::SLIDER => self.edit_selected_submenu().on_left(),
             AppPage::CONFIRMATION => {}
             AppPage::CLOSE => {}
         }
     }
     fn on_right(&mut self) {
+        if self.help_open {
+            return;
+        }
+        self.action_message = None;
         match self.page {
-            AppPage::SUBMENU => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .on_right(),
-            AppPage::TOGGLE => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_right(),
-            AppPage::SLIDER => self
-                .tabs
-                .get_selected()
-                .expect("No tab selected!")
-                .submenus
-                .get_selected()
-                .expect("No submenu selected!")
-                .on_right(),
+            AppPage::SUBMENU => self.selected_submenu_table().next_col_checked(),
+            AppPage::TOGGLE => self.edit_selected_submenu().on_right(),
+            AppPage::SLIDER => self.edit_selected_submenu().on_right(),
             AppPage::CONFIRMATION => {}
             AppPage::CLOSE => {}
         }
@@ -286,26 +576,65 @@ impl<'a> InputControl for App<'a> {
         self.page = AppPage::CLOSE;
     }
     fn on_l(&mut self) {
-        // Reset current selection to default
-        // TODO!() Confirmation
+        if self.help_open {
+            return;
+        }
+        // Switch how the slider's handles interact
+        if self.page == AppPage::SLIDER {
+            self.edit_selected_submenu().on_l();
+        }
+        // TODO!() Reset current selection to default on the other pages, with a confirmation
     }
     fn on_r(&mut self) {
+        if self.help_open {
+            return;
+        }
         // Reset all settings to default
-        // TODO!() Confirmation
-        self.load_defaults();
+        self.activate_action(ACTION_RESET_DEFAULTS);
     }
     fn on_zl(&mut self) {
+        if self.help_open {
+            return;
+        }
         match self.page {
-            AppPage::SUBMENU => {
-                self.tabs.previous();
-            }
+            AppPage::SUBMENU => self.select_tab(false),
+            AppPage::SLIDER => self.edit_selected_submenu().on_zl(),
             _ => {}
         }
     }
     fn on_zr(&mut self) {
+        if self.help_open {
+            return;
+        }
         match self.page {
-            AppPage::SUBMENU => self.tabs.next(),
+            AppPage::SUBMENU => self.select_tab(true),
+            AppPage::SLIDER => self.edit_selected_submenu().on_zr(),
             _ => {}
         }
     }
 }
+
+/// Why settings couldn't be loaded
+#[derive(Debug)]
+pub enum SettingsError {
+    Json(serde_json::Error),
+    /// A value which doesn't fit its submenu, by submenu id
+    InvalidValue(String),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::Json(err) => write!(f, "Could not parse the settings: {}", err),
+            SettingsError::InvalidValue(id) => write!(f, "{} has an invalid value", id),
+        }
+    }
+}
+
+impl core::error::Error for SettingsError {}
+
+impl From<serde_json::Error> for SettingsError {
+    fn from(err: serde_json::Error) -> SettingsError {
+        SettingsError::Json(err)
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::{collections::BTreeMap, string::String};
+
+/// The controller buttons the menu responds to, one for each InputControl method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Button {
+    A,
+    B,
+    X,
+    Y,
+    Up,
+    Down,
+    Left,
+    Right,
+    Start,
+    L,
+    R,
+    ZL,
+    ZR,
+}
+
+impl Button {
+    pub const ALL: [Button; 13] = [
+        Button::A,
+        Button::B,
+        Button::X,
+        Button::Y,
+        Button::Up,
+        Button::Down,
+        Button::Left,
+        Button::Right,
+        Button::Start,
+        Button::L,
+        Button::R,
+        Button::ZL,
+        Button::ZR,
+    ];
+
+    /// How the button is labelled on the controller
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Button::A => "A",
+            Button::B => "B",
+            Button::X => "X",
+            Button::Y => "Y",
+            Button::Up => "↑",
+            Button::Down => "↓",
+            Button::Left => "←",
+            Button::Right => "→",
+            Button::Start => "+",
+            Button::L => "L",
+            Button::R => "R",
+            Button::ZL => "ZL",
+            Button::ZR => "ZR",
+        }
+    }
+
+    /// Whether this is one of the directions, which repeat while held
+    pub fn is_direction(&self) -> bool {
+        matches!(
+            self,
+            Button::Up | Button::Down | Button::Left | Button::Right
+        )
+    }
+
+    /// Looks up a button by name, ignoring case, e.g. "zl" or "Start"
+    pub fn from_name(name: &str) -> Option<Button> {
+        let button = match name.to_ascii_uppercase().as_str() {
+            "A" => Button::A,
+            "B" => Button::B,
+            "X" => Button::X,
+            "Y" => Button::Y,
+            "UP" => Button::Up,
+            "DOWN" => Button::Down,
+            "LEFT" => Button::Left,
+            "RIGHT" => Button::Right,
+            "+" | "START" | "PLUS" => Button::Start,
+            "L" => Button::L,
+            "R" => Button::R,
+            "ZL" => Button::ZL,
+            "ZR" => Button::ZR,
+            _ => return None,
+        };
+        Some(button)
+    }
+}
+
+/// Where input is coming from, which decides how buttons are labelled in hints
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Controller,
+    /// Labels for the key bound to each button, e.g. "Enter" for A.
+    /// Buttons without a label fall back to their controller glyph.
+    Keyboard(BTreeMap<Button, String>),
+}
@@ -0,0 +1,405 @@
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+use crate::{
+    AnySlider, AnySliderValue, App, SliderFormat, SliderMode, SliderTick, SliderType, StatefulList,
+    StatefulSlider, StatefulTable, SubMenu, SubMenuType, Tab, Toggle, NX_SUBMENU_COLUMNS,
+};
+
+/// A whole menu described as data, for hosts which can't build an App in Rust:
+///
+/// ```json
+/// {"tabs": [{"id": "mash", "title": "Mash Settings", "submenus": [
+///     {"id": "di", "title": "Set DI", "type": "ToggleSingle",
+///      "toggles": [{"title": "Off", "value": 1}, {"title": "Away"}]},
+///     {"id": "dmg", "title": "Damage", "type": "Slider",
+///      "slider": {"min": 0, "max": 150, "lower": 40}},
+///     {"id": "angle", "title": "Angle", "type": "Slider",
+///      "slider": {"type": "f32", "min": -1.5, "max": 1.5, "incr_amount_slow": 0.1,
+///                 "mode": "Push", "range_handle": true}},
+///     {"id": "hitstun", "title": "Hitstun", "type": "Slider",
+///      "slider": {"min": 0, "max": 120, "presets": [0, 30, 60],
+///                 "format": {"frames_per_second": 60, "unit": "s",
+///                            "ticks": [{"value": 60, "label": "1 second"}]}}}
+/// ]}]}
+/// ```
+///
+/// Grids are as wide as `cols`, which defaults to the usual number of columns,
+/// and have as many rows as their items need.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct MenuDefinition {
+    pub tabs: Vec<TabDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct TabDefinition {
+    pub id: String,
+    pub title: String,
+    #[serde(default = "default_cols")]
+    pub cols: usize,
+    pub submenus: Vec<SubMenuDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SubMenuDefinition {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub help_text: String,
+    #[serde(rename = "type")]
+    pub submenu_type: SubMenuType,
+    #[serde(default = "default_cols")]
+    pub cols: usize,
+    /// For ToggleSingle and ToggleMultiple submenus
+    #[serde(default)]
+    pub toggles: Vec<ToggleDefinition>,
+    /// For Slider submenus
+    #[serde(default)]
+    pub slider: Option<SliderDefinition>,
+    /// For Nested submenus
+    #[serde(default)]
+    pub submenus: Vec<SubMenuDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ToggleDefinition {
+    pub title: String,
+    #[serde(default)]
+    pub value: u8,
+    #[serde(default = "default_toggle_max")]
+    pub max: u8,
+    #[serde(default)]
+    pub help_text: String,
+}
+
+/// A slider over `min..=max`, with its handles at the extremes unless given.
+/// The numbers are read as the slider's value type, which is u32 unless given.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SliderDefinition {
+    #[serde(default, rename = "type")]
+    pub value_type: SliderType,
+    pub min: Number,
+    pub max: Number,
+    #[serde(default)]
+    pub lower: Option<Number>,
+    #[serde(default)]
+    pub upper: Option<Number>,
+    #[serde(default = "default_incr_amount_slow")]
+    pub incr_amount_slow: Number,
+    #[serde(default = "default_incr_amount_fast")]
+    pub incr_amount_fast: Number,
+    /// How the handles interact, which the player can also change with L
+    #[serde(default)]
+    pub mode: SliderMode,
+    /// Whether both handles can be selected together to move the range as one
+    #[serde(default)]
+    pub range_handle: bool,
+    /// Decimal places shown in labels, if not the usual for the value type
+    #[serde(default)]
+    pub precision: Option<usize>,
+    #[serde(default)]
+    pub format: SliderFormatDefinition,
+    /// Values the selected handle can jump between, in ascending order
+    #[serde(default)]
+    pub presets: Vec<Number>,
+}
+
+/// How a slider's values are displayed, as in SliderFormat
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct SliderFormatDefinition {
+    #[serde(default)]
+    pub unit: String,
+    #[serde(default)]
+    pub frames_per_second: Option<u32>,
+    #[serde(default)]
+    pub ticks: Vec<SliderTickDefinition>,
+    #[serde(default)]
+    pub snap_to_ticks: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SliderTickDefinition {
+    pub value: Number,
+    pub label: String,
+}
+
+fn default_cols() -> usize {
+    NX_SUBMENU_COLUMNS
+}
+
+fn default_toggle_max() -> u8 {
+    1
+}
+
+fn default_incr_amount_slow() -> Number {
+    Number::from(1)
+}
+
+fn default_incr_amount_fast() -> Number {
+    Number::from(10)
+}
+
+impl MenuDefinition {
+    /// Reads and checks a definition, so that building it can't fail
+    pub fn from_json(json: &str) -> Result<MenuDefinition, DefinitionError> {
+        let definition: MenuDefinition = serde_json::from_str(json)?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    pub fn validate(&self) -> Result<(), DefinitionError> {
+        if self.tabs.is_empty() {
+            return Err(DefinitionError::NoTabs);
+        }
+        // Settings are found by submenu id, so those have to be unique across the whole menu.
+        // Saved settings are keyed by the titles of the submenus in tabs, so those have to be
+        // unique too.
+        let mut tab_ids = BTreeSet::new();
+        let mut submenu_ids = BTreeSet::new();
+        let mut submenu_titles = BTreeSet::new();
+        for tab in self.tabs.iter() {
+            if !tab_ids.insert(tab.id.as_str()) {
+                return Err(DefinitionError::DuplicateId(tab.id.clone()));
+            }
+            if tab.submenus.is_empty() {
+                return Err(DefinitionError::Empty(tab.id.clone()));
+            }
+            if tab.cols == 0 {
+                return Err(DefinitionError::NoColumns(tab.id.clone()));
+            }
+            for submenu in tab.submenus.iter() {
+                if !submenu_titles.insert(submenu.title.as_str()) {
+                    return Err(DefinitionError::DuplicateTitle(submenu.title.clone()));
+                }
+                submenu.validate(&mut submenu_ids)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds an App whose text borrows from this definition
+    pub fn build(&self) -> App<'_> {
+        let mut app = App::new();
+        app.tabs = StatefulList::with_items(
+            self.tabs
+                .iter()
+                .map(|tab| Tab {
+                    title: &tab.title,
+                    id: &tab.id,
+                    submenus: grid(tab.cols, tab.submenus.iter().map(|s| s.build()).collect()),
+                })
+                .collect(),
+        );
+        app
+    }
+}
+
+impl SubMenuDefinition {
+    fn validate<'d>(&'d self, ids: &mut BTreeSet<&'d str>) -> Result<(), DefinitionError> {
+        if !ids.insert(self.id.as_str()) {
+            return Err(DefinitionError::DuplicateId(self.id.clone()));
+        }
+        if self.cols == 0 {
+            return Err(DefinitionError::NoColumns(self.id.clone()));
+        }
+        match self.submenu_type {
+            SubMenuType::ToggleSingle | SubMenuType::ToggleMultiple => {
+                if self.toggles.is_empty() {
+                    return Err(DefinitionError::Empty(self.id.clone()));
+                }
+                if self.toggles.iter().any(|toggle| toggle.value > toggle.max) {
+                    return Err(DefinitionError::InvalidValue(self.id.clone()));
+                }
+            }
+            SubMenuType::Slider => {
+                self.slider
+                    .as_ref()
+                    .ok_or_else(|| DefinitionError::MissingSlider(self.id.clone()))?
+                    .build(&self.id)?;
+            }
+            SubMenuType::Nested => {
+                if self.submenus.is_empty() {
+                    return Err(DefinitionError::Empty(self.id.clone()));
+                }
+                for submenu in self.submenus.iter() {
+                    submenu.validate(ids)?;
+                }
+            }
+            SubMenuType::Action => {}
+            SubMenuType::None => return Err(DefinitionError::InvalidType(self.id.clone())),
+        }
+        Ok(())
+    }
+
+    fn build(&self) -> SubMenu<'_> {
+        let toggles = self
+            .toggles
+            .iter()
+            .map(|toggle| Toggle {
+                title: &toggle.title,
+                value: toggle.value,
+                max: toggle.max,
+                help_text: &toggle.help_text,
+            })
+            .collect();
+        let slider = self.slider.as_ref().map(|definition| {
+            definition
+                .build(&self.id)
+                .expect("Slider definitions are checked when read")
+        });
+        SubMenu {
+            title: &self.title,
+            id: &self.id,
+            help_text: &self.help_text,
+            submenu_type: self.submenu_type,
+            toggles: grid(self.cols, toggles),
+            slider,
+            submenus: (self.submenu_type == SubMenuType::Nested)
+                .then(|| grid(self.cols, self.submenus.iter().map(|s| s.build()).collect())),
+        }
+    }
+}
+
+impl SliderDefinition {
+    /// Builds the slider for the submenu with the given id, failing if a number
+    /// doesn't fit the value type or is out of order
+    fn build(&self, id: &str) -> Result<AnySlider, DefinitionError> {
+        Ok(match self.value_type {
+            SliderType::U8 => self.build_typed::<u8>(id)?.into(),
+            SliderType::U16 => self.build_typed::<u16>(id)?.into(),
+            SliderType::U32 => self.build_typed::<u32>(id)?.into(),
+            SliderType::U64 => self.build_typed::<u64>(id)?.into(),
+            SliderType::Usize => self.build_typed::<usize>(id)?.into(),
+            SliderType::I8 => self.build_typed::<i8>(id)?.into(),
+            SliderType::I16 => self.build_typed::<i16>(id)?.into(),
+            SliderType::I32 => self.build_typed::<i32>(id)?.into(),
+            SliderType::I64 => self.build_typed::<i64>(id)?.into(),
+            SliderType::Isize => self.build_typed::<isize>(id)?.into(),
+            SliderType::F32 => self.build_typed::<f32>(id)?.into(),
+            SliderType::F64 => self.build_typed::<f64>(id)?.into(),
+        })
+    }
+
+    fn build_typed<T: AnySliderValue>(
+        &self,
+        id: &str,
+    ) -> Result<StatefulSlider<T>, DefinitionError> {
+        let value = |number: &Number| -> Result<T, DefinitionError> {
+            serde_json::from_value(Value::Number(number.clone()))
+                .map_err(|_| DefinitionError::InvalidValue(id.to_string()))
+        };
+        let (min, max) = (value(&self.min)?, value(&self.max)?);
+        let lower = self.lower.as_ref().map_or(Ok(min), value)?;
+        let upper = self.upper.as_ref().map_or(Ok(max), value)?;
+        if !(min <= lower && lower <= upper && upper <= max) {
+            return Err(DefinitionError::InvalidValue(id.to_string()));
+        }
+        let mut slider = StatefulSlider::with_range(
+            min,
+            max,
+            value(&self.incr_amount_slow)?,
+            value(&self.incr_amount_fast)?,
+        );
+        slider.lower = lower;
+        slider.upper = upper;
+        let in_range = |number: &Number| -> Result<T, DefinitionError> {
+            let value = value(number)?;
+            if min <= value && value <= max {
+                Ok(value)
+            } else {
+                Err(DefinitionError::InvalidValue(id.to_string()))
+            }
+        };
+        let presets = self
+            .presets
+            .iter()
+            .map(in_range)
+            .collect::<Result<Vec<T>, _>>()?;
+        if presets.windows(2).any(|pair| pair[0] > pair[1])
+            || self.format.frames_per_second == Some(0)
+        {
+            return Err(DefinitionError::InvalidValue(id.to_string()));
+        }
+        let ticks = self
+            .format
+            .ticks
+            .iter()
+            .map(|tick| {
+                Ok(SliderTick {
+                    value: in_range(&tick.value)?,
+                    label: tick.label.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, DefinitionError>>()?;
+        slider.mode = self.mode;
+        slider.range_handle = self.range_handle;
+        slider.precision = self.precision;
+        slider.format = SliderFormat {
+            unit: self.format.unit.clone(),
+            frames_per_second: self.format.frames_per_second,
+            ticks,
+            snap_to_ticks: self.format.snap_to_ticks,
+        };
+        slider.presets = presets;
+        Ok(slider)
+    }
+}
+
+/// A table `cols` wide with just enough rows for the items
+fn grid<T: Clone + Serialize>(cols: usize, items: Vec<T>) -> StatefulTable<T> {
+    if items.is_empty() {
+        return StatefulTable::new(0, 0);
+    }
+    let rows = items.len().div_ceil(cols);
+    StatefulTable::with_items(rows, cols, items)
+}
+
+/// Why a menu definition couldn't be read
+#[derive(Debug)]
+pub enum DefinitionError {
+    Json(serde_json::Error),
+    NoTabs,
+    /// A tab, toggle submenu or nested submenu with nothing in it, by id
+    Empty(String),
+    /// A tab id used by another tab, or a submenu id used by another submenu
+    DuplicateId(String),
+    /// A submenu title used by another submenu in a tab
+    DuplicateTitle(String),
+    NoColumns(String),
+    /// A toggle or slider value outside of its range
+    InvalidValue(String),
+    MissingSlider(String),
+    InvalidType(String),
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DefinitionError::Json(err) => write!(f, "Could not parse the menu definition: {}", err),
+            DefinitionError::NoTabs => write!(f, "Menus need at least one tab"),
+            DefinitionError::Empty(id) => write!(f, "{} has nothing in it", id),
+            DefinitionError::DuplicateId(id) => write!(f, "{} is used more than once", id),
+            DefinitionError::DuplicateTitle(title) => {
+                write!(f, "The title {} is used more than once", title)
+            }
+            DefinitionError::NoColumns(id) => write!(f, "{} needs at least one column", id),
+            DefinitionError::InvalidValue(id) => write!(f, "{} has a value out of range", id),
+            DefinitionError::MissingSlider(id) => write!(f, "{} needs a slider", id),
+            DefinitionError::InvalidType(id) => write!(f, "{} has no submenu type", id),
+        }
+    }
+}
+
+impl core::error::Error for DefinitionError {}
+
+impl From<serde_json::Error> for DefinitionError {
+    fn from(err: serde_json::Error) -> DefinitionError {
+        DefinitionError::Json(err)
+    }
+}
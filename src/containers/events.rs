@@ -0,0 +1,196 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::mem;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{App, AppPage, StatefulTable, SubMenu};
+
+/// Something the host may want to react to while the menu is still open
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum MenuEvent {
+    /// A setting's value changed. Values are in the same form as in `App::to_json`.
+    SettingChanged {
+        submenu_id: String,
+        old: Value,
+        new: Value,
+    },
+    DefaultsSaved,
+    /// Settings were loaded all at once, from JSON or from the saved defaults.
+    /// Follows the SettingChanged events for everything the load changed.
+    PresetLoaded,
+    MenuClosed,
+}
+
+/// Where a setting is in the menu: its tab, then the ids of each Nested submenu leading to
+/// it and its own. Submenus in different tabs or Nested submenus may share ids.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct SettingPath<'a> {
+    pub tab: usize,
+    pub ids: Vec<&'a str>,
+}
+
+/// Which settings may have changed since changes were last checked for
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) enum DirtySettings<'a> {
+    #[default]
+    Clean,
+    /// Only the settings of these submenus
+    Submenus(Vec<SettingPath<'a>>),
+    All,
+}
+
+/// Callback invoked with each event as it happens
+pub type EventCallback<'a> = Box<dyn FnMut(&MenuEvent) + 'a>;
+
+impl<'a> App<'a> {
+    /// Sends every event to `callback` as it happens, starting with any still queued.
+    /// Replaces any previous subscriber.
+    pub fn subscribe(&mut self, callback: EventCallback<'a>) {
+        self.event_callback = Some(callback);
+        for event in mem::take(&mut self.event_queue) {
+            self.emit(event);
+        }
+    }
+
+    pub fn unsubscribe(&mut self) {
+        self.event_callback = None;
+    }
+
+    /// Takes the events which have happened since the last call, for hosts without a subscriber
+    pub fn drain_events(&mut self) -> Vec<MenuEvent> {
+        self.check_for_changes();
+        mem::take(&mut self.event_queue)
+    }
+
+    /// Emits events for settings which have changed and for the menu closing.
+    ///
+    /// This happens after every `handle_input` and `handle_mouse`, so hosts only need to call
+    /// it when they use the InputControl methods directly. Only the settings which input,
+    /// actions or loads may have touched are compared; hosts which change submenus
+    /// themselves should call `mark_settings_changed` first.
+    pub fn check_for_changes(&mut self) {
+        match mem::take(&mut self.dirty_settings) {
+            _ if self.settings_snapshot.is_none() => self.watch_settings(),
+            DirtySettings::Clean => {}
+            DirtySettings::Submenus(paths) => {
+                for path in paths {
+                    let value = self
+                        .tabs
+                        .items
+                        .get(path.tab)
+                        .and_then(|tab| setting_value(&tab.submenus, &path.ids));
+                    if let Some(new) = value {
+                        self.compare_setting(path, new);
+                    }
+                }
+            }
+            DirtySettings::All => {
+                for (path, new) in self.setting_values() {
+                    self.compare_setting(path, new);
+                }
+            }
+        }
+        let closed = self.page == AppPage::CLOSE;
+        if closed && !self.close_reported {
+            self.emit(MenuEvent::MenuClosed);
+        }
+        self.close_reported = closed;
+    }
+
+    /// Compares every setting against its old value the next time changes are checked for
+    pub fn mark_settings_changed(&mut self) {
+        self.dirty_settings = DirtySettings::All;
+    }
+
+    /// Compares one setting against its old value the next time changes are checked for
+    pub(crate) fn mark_setting_changed(&mut self, path: SettingPath<'a>) {
+        match &mut self.dirty_settings {
+            DirtySettings::Clean => self.dirty_settings = DirtySettings::Submenus(vec![path]),
+            DirtySettings::Submenus(paths) if !paths.contains(&path) => paths.push(path),
+            _ => {}
+        }
+    }
+
+    /// Starts comparing settings against their current values, unless that has already begun.
+    /// Settings set up directly on the submenus before the menu is first used don't count
+    /// as changes, but loads with `update_from_json` do.
+    pub(crate) fn watch_settings(&mut self) {
+        if self.settings_snapshot.is_none() {
+            self.settings_snapshot = Some(self.setting_values().into_iter().collect());
+            self.dirty_settings = DirtySettings::Clean;
+        }
+    }
+
+    /// Records a setting's current value, emitting an event if it differs from the last one.
+    /// Settings are matched up by their path rather than their position, in case the menu
+    /// was rebuilt in between.
+    fn compare_setting(&mut self, path: SettingPath<'a>, new: Value) {
+        let Some(snapshot) = self.settings_snapshot.as_mut() else {
+            return;
+        };
+        let submenu_id = path.ids.last().copied().unwrap_or_default().to_string();
+        match snapshot.insert(path, new.clone()) {
+            Some(old) if old != new => self.emit(MenuEvent::SettingChanged {
+                submenu_id,
+                old,
+                new,
+            }),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn emit(&mut self, event: MenuEvent) {
+        match self.event_callback.as_mut() {
+            Some(callback) => callback(&event),
+            None => self.event_queue.push(event),
+        }
+    }
+
+    /// The path and value of every setting, in menu order
+    fn setting_values(&self) -> Vec<(SettingPath<'a>, Value)> {
+        let mut values = vec![];
+        for (idx, tab) in self.tabs.iter().enumerate() {
+            let path = SettingPath {
+                tab: idx,
+                ids: vec![],
+            };
+            collect_values(&tab.submenus, path, &mut values);
+        }
+        values
+    }
+}
+
+/// The value of the setting at the end of a path of submenu ids
+fn setting_value(table: &StatefulTable<SubMenu>, ids: &[&str]) -> Option<Value> {
+    let (id, nested_ids) = ids.split_first()?;
+    let submenu = table.iter().find(|s| s.is_serializable() && s.id == *id)?;
+    match submenu.submenus.as_ref() {
+        Some(submenus) => setting_value(submenus, nested_ids),
+        None if nested_ids.is_empty() => {
+            Some(serde_json::to_value(submenu).expect("Could not serialize the setting!"))
+        }
+        None => None,
+    }
+}
+
+fn collect_values<'a>(
+    table: &StatefulTable<SubMenu<'a>>,
+    path: SettingPath<'a>,
+    values: &mut Vec<(SettingPath<'a>, Value)>,
+) {
+    for submenu in table.iter().filter(|s| s.is_serializable()) {
+        let mut path = path.clone();
+        path.ids.push(submenu.id);
+        if let Some(submenus) = submenu.submenus.as_ref() {
+            collect_values(submenus, path, values);
+        } else {
+            let value = serde_json::to_value(submenu).expect("Could not serialize the setting!");
+            values.push((path, value));
+        }
+    }
+}
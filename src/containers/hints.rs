@@ -0,0 +1,108 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{App, AppPage, Button, InputSource, SubMenuType};
+
+/// An action available right now, and the button which performs it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ButtonHint {
+    pub button: Button,
+    pub action: &'static str,
+}
+
+impl ButtonHint {
+    fn new(button: Button, action: &'static str) -> ButtonHint {
+        ButtonHint { button, action }
+    }
+}
+
+impl<'a> App<'a> {
+    /// The buttons which do something in the current state, and what they do
+    pub fn button_hints(&self) -> Vec<ButtonHint> {
+        if self.help_open {
+            return vec![
+                ButtonHint::new(Button::B, "Close help"),
+                ButtonHint::new(Button::Y, "Close help"),
+            ];
+        }
+        let mut hints = vec![];
+        match self.page {
+            AppPage::SUBMENU => {
+                let submenu = self.current_submenu();
+                let select = match submenu.submenu_type {
+                    SubMenuType::ToggleSingle
+                    | SubMenuType::ToggleMultiple
+                    | SubMenuType::Slider => Some("Open"),
+                    SubMenuType::Nested if submenu.has_nested() => Some("Open"),
+                    SubMenuType::Action => Some("Run"),
+                    SubMenuType::Nested | SubMenuType::None => None,
+                };
+                if let Some(action) = select {
+                    hints.push(ButtonHint::new(Button::A, action));
+                }
+                let back = if self.submenu_depth > 0 {
+                    "Back"
+                } else {
+                    "Exit"
+                };
+                hints.push(ButtonHint::new(Button::B, back));
+                hints.push(ButtonHint::new(Button::X, "Save defaults"));
+                hints.push(ButtonHint::new(Button::Y, "Help"));
+                hints.push(ButtonHint::new(Button::R, "Reset all"));
+                if self.tabs.items.len() > 1 {
+                    hints.push(ButtonHint::new(Button::ZL, "Prev tab"));
+                    hints.push(ButtonHint::new(Button::ZR, "Next tab"));
+                }
+            }
+            AppPage::TOGGLE => {
+                let action = match self.current_submenu().submenu_type {
+                    SubMenuType::ToggleSingle => "Select",
+                    _ => "Toggle",
+                };
+                hints.push(ButtonHint::new(Button::A, action));
+                hints.push(ButtonHint::new(Button::B, "Back"));
+                hints.push(ButtonHint::new(Button::X, "Save defaults"));
+                hints.push(ButtonHint::new(Button::Y, "Help"));
+                hints.push(ButtonHint::new(Button::R, "Reset all"));
+            }
+            AppPage::SLIDER => {
+                let slider = self
+                    .current_submenu()
+                    .slider
+                    .as_ref()
+                    .expect("No slider selected!");
+                // Whatever the slider doesn't use is handled by the app
+                for button in Button::ALL {
+                    let action = match (slider.action(button), button) {
+                        (Some(action), _) => action.description(),
+                        (None, Button::B) => "Back",
+                        (None, Button::Y) => "Help",
+                        (None, Button::R) => "Reset all",
+                        (None, _) => continue,
+                    };
+                    hints.push(ButtonHint::new(button, action));
+                }
+            }
+            AppPage::CONFIRMATION => {
+                hints.push(ButtonHint::new(Button::A, "Confirm"));
+                hints.push(ButtonHint::new(Button::B, "Cancel"));
+            }
+            AppPage::CLOSE => {}
+        }
+        hints
+    }
+
+    /// How to show a button to the player, depending on the input source
+    pub fn button_label(&self, button: Button) -> String {
+        match &self.input_source {
+            InputSource::Keyboard(labels) => match labels.get(&button) {
+                Some(label) => label.clone(),
+                None => button.glyph().to_string(),
+            },
+            InputSource::Controller => button.glyph().to_string(),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{App, AppPage, Button, InputControl};
+
+/// What happened to a button
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ButtonEvent {
+    Press,
+    Release,
+    /// The button is still down, one tick after its Press or previous Hold.
+    /// Hosts which poll the controller send this every frame that a button stays down,
+    /// and others can call `App::tick` instead.
+    Hold,
+}
+
+impl<'a> App<'a> {
+    /// The single entry point for button input, whether it comes from the game, a terminal
+    /// or a test script.
+    ///
+    /// A Press acts straight away, the same as calling the button's InputControl method.
+    /// Holds and releases keep track of which buttons are down and for how long,
+    /// and held directions repeat according to `auto_repeat`.
+    /// Any changes to settings are then reported as events.
+    pub fn handle_input(&mut self, button: Button, event: ButtonEvent) {
+        self.watch_settings();
+        match event {
+            ButtonEvent::Press => {
+                self.held_buttons.insert(button, 0);
+                self.on_button(button);
+            }
+            ButtonEvent::Hold => {
+                // A hold without a press, e.g. a button which was already down when the menu
+                // opened, is tracked from now on but doesn't act straight away
+                let held_ticks = self.held_buttons.entry(button).or_insert(0);
+                *held_ticks = held_ticks.saturating_add(1);
+                let held_ticks = *held_ticks;
+                if button.is_direction() && self.auto_repeat.repeats_at(held_ticks) {
+                    self.repeat_direction(button, held_ticks);
+                }
+            }
+            ButtonEvent::Release => {
+                self.held_buttons.remove(&button);
+            }
+        }
+        self.check_for_changes();
+    }
+
+    /// Holds every button which is down for another tick, for hosts which know which buttons
+    /// are down but don't send a Hold for each of them
+    pub fn tick(&mut self) {
+        for button in Button::ALL {
+            if self.is_held(button) {
+                self.handle_input(button, ButtonEvent::Hold);
+            }
+        }
+    }
+
+    pub fn is_held(&self, button: Button) -> bool {
+        self.held_buttons.contains_key(&button)
+    }
+
+    /// Number of Hold events since the button was pressed, or None if it isn't down
+    pub fn hold_count(&self, button: Button) -> Option<u32> {
+        self.held_buttons.get(&button).copied()
+    }
+
+    /// Repeats a held direction. Left and right move a selected slider handle by the slow
+    /// increment until the direction has been held long enough to switch to the fast one.
+    /// The steps bypass the slider's StepAcceleration, which is only for separate taps.
+    fn repeat_direction(&mut self, button: Button, held_ticks: u32) {
+        let horizontal = matches!(button, Button::Left | Button::Right);
+        if horizontal && self.page == AppPage::SLIDER && !self.help_open {
+            let fast = self.auto_repeat.is_fast(held_ticks);
+            let slider = self
+                .edit_selected_submenu()
+                .slider
+                .as_mut()
+                .expect("No slider selected!");
+            if slider.is_handle_selected() && !slider.is_entering() {
+                match (button == Button::Right, fast) {
+                    (true, false) => slider.increment_selected_slow(),
+                    (true, true) => slider.increment_selected_fast(),
+                    (false, false) => slider.decrement_selected_slow(),
+                    (false, true) => slider.decrement_selected_fast(),
+                }
+                return;
+            }
+        }
+        self.on_button(button);
+    }
+}
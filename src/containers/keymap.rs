@@ -0,0 +1,447 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+use serde_json::Value;
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+use crate::Button;
+
+/// A key on the keyboard, independent of any terminal library
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A printable character. Shifted letters are their upper-case characters.
+    Char(char),
+    Enter,
+    Esc,
+    Backspace,
+    Tab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    F(u8),
+}
+
+/// A key with any modifiers held along with it, e.g. "ctrl+s"
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// Only used for keys which aren't characters, since shifted characters are already distinct
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub fn new(key: Key) -> KeyChord {
+        KeyChord {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    pub fn char(c: char) -> KeyChord {
+        KeyChord::new(Key::Char(c))
+    }
+
+    pub fn ctrl(key: Key) -> KeyChord {
+        KeyChord {
+            ctrl: true,
+            ..KeyChord::new(key)
+        }
+    }
+
+    /// Parses names like "a", "Enter", "space", "F5" or "ctrl+alt+Left", ignoring the case of
+    /// everything but single characters. "shift+a" is the same as "A".
+    pub fn parse(text: &str) -> Result<KeyChord, KeymapError> {
+        let invalid = || KeymapError::InvalidKey(text.to_string());
+        let mut parts: Vec<&str> = text.split('+').collect();
+        // A trailing "+" is the plus key itself, e.g. "ctrl++"
+        if text.ends_with("++") || text == "+" {
+            parts.pop();
+            parts.pop();
+            parts.push("+");
+        }
+        let name = parts.pop().ok_or_else(invalid)?;
+        let mut chord = KeyChord::new(parse_key(name).ok_or_else(invalid)?);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" => chord.alt = true,
+                "shift" => chord.shift = true,
+                _ => return Err(invalid()),
+            }
+        }
+        if let (Key::Char(c), true) = (chord.key, chord.shift) {
+            chord.key = Key::Char(c.to_ascii_uppercase());
+            chord.shift = false;
+        }
+        Ok(chord)
+    }
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
+    }
+    let key = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => Key::Enter,
+        "esc" | "escape" => Key::Esc,
+        "backspace" => Key::Backspace,
+        "tab" => Key::Tab,
+        "space" => Key::Char(' '),
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        lower => {
+            let number: u8 = lower.strip_prefix('f')?.parse().ok()?;
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            Key::F(number)
+        }
+    };
+    Some(key)
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "alt+")?;
+        }
+        if self.shift {
+            write!(f, "shift+")?;
+        }
+        match self.key {
+            Key::Char(' ') => write!(f, "Space"),
+            Key::Char(c) => write!(f, "{}", c),
+            Key::F(number) => write!(f, "F{}", number),
+            key => write!(f, "{:?}", key),
+        }
+    }
+}
+
+/// What a key does: press a button, or show the list of key bindings
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyAction {
+    Press(Button),
+    ShowBindings,
+}
+
+impl KeyAction {
+    /// Every action, in the order they are listed in the bindings overlay
+    pub fn all() -> Vec<KeyAction> {
+        let mut actions: Vec<KeyAction> =
+            Button::ALL.iter().map(|b| KeyAction::Press(*b)).collect();
+        actions.push(KeyAction::ShowBindings);
+        actions
+    }
+
+    /// Looks up an action by name, ignoring case: a button name such as "zl", or "bindings"
+    pub fn from_name(name: &str) -> Option<KeyAction> {
+        if name.eq_ignore_ascii_case("bindings") {
+            return Some(KeyAction::ShowBindings);
+        }
+        Button::from_name(name).map(KeyAction::Press)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Press(button) => button.glyph(),
+            KeyAction::ShowBindings => "Key bindings",
+        }
+    }
+}
+
+/// Maps keys to actions. Each action can have several keys, but each key has one action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keymap {
+    pub name: String,
+    bindings: BTreeMap<KeyAction, Vec<KeyChord>>,
+}
+
+impl Keymap {
+    pub fn new(name: &str) -> Keymap {
+        Keymap {
+            name: name.to_string(),
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    /// Binds `keys` to `action`, replacing its previous keys.
+    /// A key which was bound to another action is moved to this one.
+    pub fn bind(&mut self, action: KeyAction, keys: Vec<KeyChord>) {
+        for other in self.bindings.values_mut() {
+            other.retain(|key| !keys.contains(key));
+        }
+        self.bindings.insert(action, keys);
+    }
+
+    pub fn action(&self, key: KeyChord) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: KeyAction) -> &[KeyChord] {
+        self.bindings
+            .get(&action)
+            .map_or(&[], |keys| keys.as_slice())
+    }
+
+    /// The first key bound to each button, for labelling button hints
+    pub fn labels(&self) -> BTreeMap<Button, String> {
+        Button::ALL
+            .iter()
+            .filter_map(|button| {
+                let key = self.keys(KeyAction::Press(*button)).first()?;
+                Some((*button, key.to_string()))
+            })
+            .collect()
+    }
+
+    /// Every action with the keys bound to it, in a fixed order
+    pub fn entries(&self) -> Vec<(KeyAction, &[KeyChord])> {
+        KeyAction::all()
+            .into_iter()
+            .map(|action| (action, self.keys(action)))
+            .collect()
+    }
+
+    /// The keys the terminal frontend has always used
+    pub fn default_preset() -> Keymap {
+        use Key::*;
+        let mut keymap = Keymap::new("Default");
+        keymap.bind_all(&[
+            (Button::A, &[Char('a'), Enter]),
+            (Button::B, &[Char('b'), Backspace]),
+            (Button::X, &[Char('x')]),
+            (Button::Y, &[Char('y')]),
+            (Button::Up, &[Up]),
+            (Button::Down, &[Down]),
+            (Button::Left, &[Left]),
+            (Button::Right, &[Right]),
+            (Button::Start, &[Char('q')]),
+            (Button::L, &[Char('l')]),
+            (Button::R, &[Char('r')]),
+            (Button::ZL, &[Char('o')]),
+            (Button::ZR, &[Char('p')]),
+        ]);
+        keymap.bind(KeyAction::ShowBindings, vec![KeyChord::char('?')]);
+        keymap
+    }
+
+    /// hjkl to move, with the shoulder buttons on the bracket keys
+    pub fn vim() -> Keymap {
+        use Key::*;
+        let mut keymap = Keymap::new("Vim");
+        keymap.bind_all(&[
+            (Button::A, &[Enter, Char(' '), Char('a')]),
+            (Button::B, &[Esc, Backspace, Char('b')]),
+            (Button::X, &[Char('x')]),
+            (Button::Y, &[Char('y')]),
+            (Button::Up, &[Char('k'), Up]),
+            (Button::Down, &[Char('j'), Down]),
+            (Button::Left, &[Char('h'), Left]),
+            (Button::Right, &[Char('l'), Right]),
+            (Button::Start, &[Char('q')]),
+            (Button::L, &[Char('{')]),
+            (Button::R, &[Char('}')]),
+            (Button::ZL, &[Char('[')]),
+            (Button::ZR, &[Char(']')]),
+        ]);
+        keymap.bind(KeyAction::ShowBindings, vec![KeyChord::char('?')]);
+        keymap
+    }
+
+    /// Only the arrow keys and the navigation keys around them
+    pub fn arrows() -> Keymap {
+        use Key::*;
+        let mut keymap = Keymap::new("Arrows");
+        keymap.bind_all(&[
+            (Button::A, &[Enter]),
+            (Button::B, &[Esc, Backspace]),
+            (Button::X, &[Tab]),
+            (Button::Y, &[F(1)]),
+            (Button::Up, &[Up]),
+            (Button::Down, &[Down]),
+            (Button::Left, &[Left]),
+            (Button::Right, &[Right]),
+            (Button::Start, &[F(10)]),
+            (Button::L, &[Home]),
+            (Button::R, &[End]),
+            (Button::ZL, &[PageUp]),
+            (Button::ZR, &[PageDown]),
+        ]);
+        keymap.bind(KeyAction::ShowBindings, vec![KeyChord::new(F(2))]);
+        keymap
+    }
+
+    /// WASD to move with the left hand, and the buttons around them
+    pub fn wasd() -> Keymap {
+        use Key::*;
+        let mut keymap = Keymap::new("WASD");
+        keymap.bind_all(&[
+            (Button::A, &[Char(' '), Enter]),
+            (Button::B, &[Char('f'), Backspace]),
+            (Button::X, &[Char('x')]),
+            (Button::Y, &[Char('c')]),
+            (Button::Up, &[Char('w'), Up]),
+            (Button::Down, &[Char('s'), Down]),
+            (Button::Left, &[Char('a'), Left]),
+            (Button::Right, &[Char('d'), Right]),
+            (Button::Start, &[Esc]),
+            (Button::L, &[Char('q')]),
+            (Button::R, &[Char('e')]),
+            (Button::ZL, &[Char('1')]),
+            (Button::ZR, &[Char('3')]),
+        ]);
+        keymap.bind(KeyAction::ShowBindings, vec![KeyChord::char('?')]);
+        keymap
+    }
+
+    pub fn presets() -> Vec<Keymap> {
+        vec![
+            Keymap::default_preset(),
+            Keymap::vim(),
+            Keymap::arrows(),
+            Keymap::wasd(),
+        ]
+    }
+
+    /// Looks up a preset by name, ignoring case
+    pub fn preset(name: &str) -> Option<Keymap> {
+        Keymap::presets()
+            .into_iter()
+            .find(|keymap| keymap.name.eq_ignore_ascii_case(name))
+    }
+
+    fn bind_all(&mut self, bindings: &[(Button, &[Key])]) {
+        for (button, keys) in bindings {
+            let keys = keys.iter().map(|key| KeyChord::new(*key)).collect();
+            self.bind(KeyAction::Press(*button), keys);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Keymap, KeymapError> {
+        Keymap::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Reads a keymap such as `{"base": "vim", "zl": ["ctrl+h", "u"], "bindings": "F1"}`.
+    /// Each action listed replaces the keys it had in the base preset, which is "default"
+    /// if not given.
+    pub fn from_json(json: &str) -> Result<Keymap, KeymapError> {
+        let value: Value = serde_json::from_str(json)?;
+        let fields = value.as_object().ok_or(KeymapError::NotAnObject)?;
+        let mut keymap = match fields.get("base") {
+            Some(Value::String(base)) => {
+                Keymap::preset(base).ok_or_else(|| KeymapError::UnknownBase(base.clone()))?
+            }
+            Some(_) => return Err(KeymapError::NotAString("base".to_string())),
+            None => Keymap::default_preset(),
+        };
+        for (key, value) in fields.iter() {
+            match key.as_str() {
+                "base" => {}
+                "name" => {
+                    keymap.name = value
+                        .as_str()
+                        .ok_or_else(|| KeymapError::NotAString(key.clone()))?
+                        .to_string()
+                }
+                _ => {
+                    let action = KeyAction::from_name(key)
+                        .ok_or_else(|| KeymapError::UnknownAction(key.clone()))?;
+                    let names = match value {
+                        Value::String(name) => vec![name.as_str()],
+                        Value::Array(names) => names
+                            .iter()
+                            .map(|name| name.as_str())
+                            .collect::<Option<Vec<&str>>>()
+                            .ok_or_else(|| KeymapError::NotAString(key.clone()))?,
+                        _ => return Err(KeymapError::NotAString(key.clone())),
+                    };
+                    let keys = names
+                        .into_iter()
+                        .map(KeyChord::parse)
+                        .collect::<Result<Vec<KeyChord>, KeymapError>>()?;
+                    keymap.bind(action, keys);
+                }
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap::default_preset()
+    }
+}
+
+/// Why a keymap file couldn't be loaded
+#[derive(Debug)]
+pub enum KeymapError {
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The keymap isn't a JSON object
+    NotAnObject,
+    /// A value which should be a key name, or a list of them, is something else
+    NotAString(String),
+    UnknownBase(String),
+    UnknownAction(String),
+    InvalidKey(String),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            KeymapError::Io(err) => write!(f, "Could not read the keymap file: {}", err),
+            KeymapError::Json(err) => write!(f, "Could not parse the keymap file: {}", err),
+            KeymapError::NotAnObject => write!(f, "Keymaps must be JSON objects"),
+            KeymapError::NotAString(key) => write!(f, "Expected key names for {}", key),
+            KeymapError::UnknownBase(name) => write!(f, "No preset keymap named {}", name),
+            KeymapError::UnknownAction(name) => write!(f, "Unknown action {}", name),
+            KeymapError::InvalidKey(name) => write!(f, "Invalid key {}", name),
+        }
+    }
+}
+
+impl core::error::Error for KeymapError {}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for KeymapError {
+    fn from(err: io::Error) -> KeymapError {
+        KeymapError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for KeymapError {
+    fn from(err: serde_json::Error) -> KeymapError {
+        KeymapError::Json(err)
+    }
+}
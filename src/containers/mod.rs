@@ -1,11 +1,33 @@
+mod action;
 mod app;
+mod button;
+mod definition;
+mod events;
+mod hints;
+mod input;
+mod keymap;
+#[cfg(feature = "has_terminal")]
+mod mouse;
+mod session;
 mod submenu;
 mod tab;
 mod toggle;
+mod view;
+pub use action::*;
 pub use app::*;
+pub use button::*;
+pub use definition::*;
+pub use events::*;
+pub use hints::*;
+pub use input::*;
+pub use keymap::*;
+#[cfg(feature = "has_terminal")]
+pub use mouse::*;
+pub use session::*;
 pub use submenu::*;
 pub use tab::*;
 pub use toggle::*;
+pub use view::*;
 
 pub trait InputControl {
     fn on_a(&mut self);
@@ -21,4 +43,23 @@ pub trait InputControl {
     fn on_r(&mut self);
     fn on_zl(&mut self);
     fn on_zr(&mut self);
+
+    /// Calls the method for `button`
+    fn on_button(&mut self, button: Button) {
+        match button {
+            Button::A => self.on_a(),
+            Button::B => self.on_b(),
+            Button::X => self.on_x(),
+            Button::Y => self.on_y(),
+            Button::Up => self.on_up(),
+            Button::Down => self.on_down(),
+            Button::Left => self.on_left(),
+            Button::Right => self.on_right(),
+            Button::Start => self.on_start(),
+            Button::L => self.on_l(),
+            Button::R => self.on_r(),
+            Button::ZL => self.on_zl(),
+            Button::ZR => self.on_zr(),
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{AnySlider, App, AppPage, HitTarget, InputControl, UiLayout, UiState};
+
+/// Mouse input, in terminal cells. Only the left button is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseInput {
+    Press { x: u16, y: u16 },
+    Drag { x: u16, y: u16 },
+    Release,
+    ScrollUp,
+    ScrollDown,
+}
+
+impl<'a> App<'a> {
+    /// Handles mouse input against the layout returned by the last call to `render_ui`,
+    /// with the UiState it was drawn with.
+    ///
+    /// - Clicking a tab switches to it
+    /// - Clicking a submenu selects it, and clicking it again opens it
+    /// - Clicking a toggle selects and toggles it
+    /// - Pressing on the slider grabs the nearest handle, which follows the mouse until released
+    ///   and then stays selected
+    /// - Scrolling switches tabs, or moves the selected slider handle
+    pub fn handle_mouse(
+        &mut self,
+        ui_layout: &UiLayout,
+        ui_state: &mut UiState,
+        input: MouseInput,
+    ) {
+        self.watch_settings();
+        if self.help_open {
+            match input {
+                MouseInput::ScrollUp => self.on_up(),
+                MouseInput::ScrollDown => self.on_down(),
+                _ => {}
+            }
+            return;
+        }
+        match input {
+            MouseInput::Press { x, y } => match ui_layout.hit_test(x, y) {
+                Some(HitTarget::Tab(idx)) => self.click_tab(idx),
+                Some(HitTarget::Cell { row, col }) => self.click_cell(row, col),
+                Some(HitTarget::Slider(fraction)) => {
+                    ui_state.slider_dragging = self.grab_slider(fraction)
+                }
+                None => {}
+            },
+            MouseInput::Drag { x, .. } => {
+                if ui_state.slider_dragging {
+                    let fraction = ui_layout.slider_fraction(x);
+                    if let Some(slider) = self.open_slider() {
+                        slider.drag_selected_to_fraction(fraction);
+                    }
+                }
+            }
+            MouseInput::Release => {
+                // The dragged handle stays selected, ready for fine adjustment
+                ui_state.slider_dragging = false;
+            }
+            MouseInput::ScrollUp => self.scroll(true),
+            MouseInput::ScrollDown => self.scroll(false),
+        }
+        self.check_for_changes();
+    }
+
+    fn click_tab(&mut self, idx: usize) {
+        if self.page != AppPage::SUBMENU || idx >= self.tabs.items.len() {
+            return;
+        }
+        self.tabs.state.select(Some(idx));
+        // Nested submenus belong to the tab we just left
+        self.submenu_depth = 0;
+    }
+
+    fn click_cell(&mut self, row: usize, col: usize) {
+        match self.page {
+            AppPage::SUBMENU => {
+                let table = self.selected_submenu_table();
+                if table.get(row, col).is_none() {
+                    return;
+                }
+                let already_selected = table.state.selected_row() == Some(row)
+                    && table.state.selected_col() == Some(col);
+                table.select(row, col);
+                if already_selected {
+                    self.on_a();
+                }
+            }
+            AppPage::TOGGLE => {
+                let toggles = &mut self.selected_submenu().toggles;
+                if toggles.get(row, col).is_none() {
+                    return;
+                }
+                toggles.select(row, col);
+                self.on_a();
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns whether a handle was grabbed, and so follows the mouse
+    fn grab_slider(&mut self, fraction: f64) -> bool {
+        match self.open_slider() {
+            Some(slider) if !slider.is_entering() => {
+                slider.grab_nearest_at(fraction);
+                slider.drag_selected_to_fraction(fraction);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn scroll(&mut self, up: bool) {
+        match self.page {
+            AppPage::SUBMENU => {
+                if up {
+                    self.on_zl();
+                } else {
+                    self.on_zr();
+                }
+            }
+            AppPage::SLIDER => {
+                if let Some(slider) = self.open_slider() {
+                    if slider.is_entering() {
+                        return;
+                    }
+                    // Scrolling over an unselected slider moves the hovered handle
+                    if !slider.is_handle_selected() {
+                        slider.select_deselect();
+                    }
+                    if up {
+                        slider.increment_selected_slow();
+                    } else {
+                        slider.decrement_selected_slow();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn open_slider(&mut self) -> Option<&mut AnySlider> {
+        if self.page != AppPage::SLIDER {
+            return None;
+        }
+        self.edit_selected_submenu().slider.as_mut()
+    }
+}
@@ -0,0 +1,167 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{App, AppPage, StatefulTable, SubMenu, SubMenuType};
+
+/// Where the player was in the menu, so that it can reopen in the same place.
+/// This is kept apart from the settings, and the host decides whether to save it.
+///
+/// Positions are saved along with the ids of what was there. When restoring into a menu
+/// whose layout has changed, selections follow the ids, then fall back to the nearest
+/// position which still exists.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub selected_tab: usize,
+    /// The selection in each tab, in tab order
+    pub tabs: Vec<TabSession>,
+    /// The selection in each opened Nested submenu of the selected tab, outermost first
+    pub open_submenus: Vec<CellSession>,
+    pub page: AppPage,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TabSession {
+    pub id: String,
+    pub selection: CellSession,
+}
+
+/// A selected cell, and the id of the submenu in it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellSession {
+    pub row: usize,
+    pub col: usize,
+    pub id: String,
+}
+
+impl<'a> App<'a> {
+    pub fn session_state(&self) -> SessionState {
+        let tabs = self
+            .tabs
+            .iter()
+            .map(|tab| TabSession {
+                id: tab.id.to_string(),
+                selection: cell_session(&tab.submenus),
+            })
+            .collect();
+        let mut open_submenus = vec![];
+        let mut table = &self.current_tab().submenus;
+        for _ in 0..self.submenu_depth {
+            table = table
+                .selected_item()
+                .expect("No submenu selected!")
+                .submenus
+                .as_ref()
+                .expect("Not a Nested submenu!");
+            open_submenus.push(cell_session(table));
+        }
+        SessionState {
+            selected_tab: self.tabs.state.selected().unwrap_or(0),
+            tabs,
+            open_submenus,
+            // A pending confirmation isn't restored, so return to the page it came from
+            page: match self.page {
+                AppPage::CONFIRMATION => self.confirmation_return,
+                page => page,
+            },
+        }
+    }
+
+    pub fn session_to_json(&self) -> String {
+        serde_json::to_string(&self.session_state())
+            .expect("Could not serialize the session to JSON!")
+    }
+
+    /// Returns to the place described by a saved session, as far as the current menu allows.
+    /// Pages which can't be reopened, such as a confirmation prompt, return to the list of
+    /// submenus instead.
+    pub fn restore_session(&mut self, session: &SessionState) {
+        if self.tabs.items.is_empty() {
+            return;
+        }
+        for (idx, saved) in session.tabs.iter().enumerate() {
+            if let Some(tab_idx) = self.find_tab(idx, &saved.id) {
+                restore_cell(&mut self.tabs.items[tab_idx].submenus, &saved.selection);
+            }
+        }
+        let selected = session
+            .tabs
+            .get(session.selected_tab)
+            .and_then(|saved| self.find_tab(session.selected_tab, &saved.id))
+            .unwrap_or_else(|| session.selected_tab.min(self.tabs.items.len() - 1));
+        self.tabs.state.select(Some(selected));
+
+        // Reopen Nested submenus for as long as the selections still lead to them
+        self.submenu_depth = 0;
+        for saved in session.open_submenus.iter() {
+            let submenu = self.selected_submenu();
+            match submenu.submenus.as_mut() {
+                Some(submenus) if submenu.submenu_type == SubMenuType::Nested => {
+                    if submenus.is_empty() {
+                        break;
+                    }
+                    restore_cell(submenus, saved);
+                }
+                _ => break,
+            }
+            self.submenu_depth += 1;
+        }
+
+        let submenu_type = self.selected_submenu().submenu_type;
+        self.page = match (session.page, submenu_type) {
+            (AppPage::TOGGLE, SubMenuType::ToggleSingle | SubMenuType::ToggleMultiple) => {
+                AppPage::TOGGLE
+            }
+            (AppPage::SLIDER, SubMenuType::Slider) => AppPage::SLIDER,
+            _ => AppPage::SUBMENU,
+        };
+        self.pending_action = None;
+        self.close_help();
+    }
+
+    /// Restores a session saved with `session_to_json`
+    pub fn restore_session_from_json(&mut self, json: &str) -> serde_json::Result<()> {
+        let session: SessionState = serde_json::from_str(json)?;
+        self.restore_session(&session);
+        Ok(())
+    }
+
+    /// The tab a saved tab refers to: the one at the same index if its id still matches,
+    /// otherwise the first with the same id
+    fn find_tab(&self, idx: usize, id: &str) -> Option<usize> {
+        match self.tabs.items.get(idx) {
+            Some(tab) if tab.id == id => Some(idx),
+            _ => self.tabs.items.iter().position(|tab| tab.id == id),
+        }
+    }
+}
+
+fn cell_session(table: &StatefulTable<SubMenu>) -> CellSession {
+    let row = table.state.selected_row().unwrap_or(0);
+    let col = table.state.selected_col().unwrap_or(0);
+    CellSession {
+        row,
+        col,
+        id: table
+            .get(row, col)
+            .map_or(String::new(), |submenu| submenu.id.to_string()),
+    }
+}
+
+fn restore_cell(table: &mut StatefulTable<SubMenu>, saved: &CellSession) {
+    let mut found = None;
+    for (row_idx, row) in table.items.iter().enumerate() {
+        for (col_idx, item) in row.iter().enumerate() {
+            if found.is_none() && item.as_ref().is_some_and(|s| s.id == saved.id) {
+                found = Some((row_idx, col_idx));
+            }
+        }
+    }
+    match found {
+        Some((row, col)) => table.select(row, col),
+        None => table.select_nearest(saved.row, saved.col),
+    }
+}
@@ -1,7 +1,13 @@
-use serde::ser::Serializer;
-use serde::Serialize;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::{InputControl, StatefulSlider, StatefulTable, Toggle};
+use crate::{AnySlider, Button, InputControl, SettingsError, StatefulTable, Toggle};
 
 #[derive(Clone)]
 pub struct SubMenu<'a> {
@@ -10,7 +16,9 @@ pub struct SubMenu<'a> {
     pub help_text: &'a str,
     pub submenu_type: SubMenuType,
     pub toggles: StatefulTable<Toggle<'a>>,
-    pub slider: Option<StatefulSlider>,
+    pub slider: Option<AnySlider>,
+    /// The grid of submenus one level down, for Nested submenus only
+    pub submenus: Option<StatefulTable<SubMenu<'a>>>,
 }
 
 impl<'a> Serialize for SubMenu<'a> {
@@ -23,6 +31,15 @@ impl<'a> Serialize for SubMenu<'a> {
                 self.toggles.serialize(serializer)
             }
             SubMenuType::Slider => self.slider.serialize(serializer),
+            SubMenuType::Nested => {
+                // Serializes as a mapping between the nested submenu ids and values
+                let mut map = serializer.serialize_map(Some(self.serializable_len()))?;
+                for submenu in self.nested_iter().filter(|s| s.is_serializable()) {
+                    map.serialize_entry(submenu.id, submenu)?;
+                }
+                map.end()
+            }
+            SubMenuType::Action => serializer.serialize_unit(),
             SubMenuType::None => panic!("At the disco"),
         }
     }
@@ -40,10 +57,9 @@ impl<'a> InputControl for SubMenu<'a> {
                 self.selected_toggle().increment();
             }
             SubMenuType::ToggleMultiple => self.selected_toggle().increment(),
-            SubMenuType::Slider => {
-                let slider = self.slider.as_mut().expect("No slider selected!");
-                slider.select_deselect();
-            }
+            SubMenuType::Slider => self.slider_input(Button::A),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
@@ -51,22 +67,25 @@ impl<'a> InputControl for SubMenu<'a> {
         match self.submenu_type {
             SubMenuType::ToggleSingle => {}
             SubMenuType::ToggleMultiple => {}
-            SubMenuType::Slider => {
-                let slider = self.slider.as_mut().expect("No slider selected!");
-                if slider.is_handle_selected() {
-                    slider.deselect()
-                }
-            }
+            SubMenuType::Slider => self.slider_input(Button::B),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
-    fn on_x(&mut self) {}
+    fn on_x(&mut self) {
+        if matches!(self.submenu_type, SubMenuType::Slider) {
+            self.slider_input(Button::X);
+        }
+    }
     fn on_y(&mut self) {}
     fn on_up(&mut self) {
         match self.submenu_type {
             SubMenuType::ToggleSingle => self.toggles.prev_row_checked(),
             SubMenuType::ToggleMultiple => self.toggles.prev_row_checked(),
-            SubMenuType::Slider => {}
+            SubMenuType::Slider => self.slider_input(Button::Up),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
@@ -74,7 +93,9 @@ impl<'a> InputControl for SubMenu<'a> {
         match self.submenu_type {
             SubMenuType::ToggleSingle => self.toggles.next_row_checked(),
             SubMenuType::ToggleMultiple => self.toggles.next_row_checked(),
-            SubMenuType::Slider => {}
+            SubMenuType::Slider => self.slider_input(Button::Down),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
@@ -82,14 +103,9 @@ impl<'a> InputControl for SubMenu<'a> {
         match self.submenu_type {
             SubMenuType::ToggleSingle => self.toggles.prev_col_checked(),
             SubMenuType::ToggleMultiple => self.toggles.prev_col_checked(),
-            SubMenuType::Slider => {
-                let slider = self.slider.as_mut().expect("No slider selected!");
-                if slider.is_handle_selected() {
-                    slider.decrement_selected_slow();
-                } else {
-                    slider.switch_hover();
-                }
-            }
+            SubMenuType::Slider => self.slider_input(Button::Left),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
@@ -97,29 +113,166 @@ impl<'a> InputControl for SubMenu<'a> {
         match self.submenu_type {
             SubMenuType::ToggleSingle => self.toggles.next_col_checked(),
             SubMenuType::ToggleMultiple => self.toggles.next_col_checked(),
-            SubMenuType::Slider => {
-                let slider = self.slider.as_mut().expect("No slider selected!");
-                if slider.is_handle_selected() {
-                    slider.increment_selected_slow();
-                } else {
-                    slider.switch_hover();
-                }
-            }
+            SubMenuType::Slider => self.slider_input(Button::Right),
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
     fn on_start(&mut self) {}
-    fn on_l(&mut self) {}
+    fn on_l(&mut self) {
+        if matches!(self.submenu_type, SubMenuType::Slider) {
+            self.slider_input(Button::L);
+        }
+    }
     fn on_r(&mut self) {}
-    fn on_zl(&mut self) {}
-    fn on_zr(&mut self) {}
+    fn on_zl(&mut self) {
+        if matches!(self.submenu_type, SubMenuType::Slider) {
+            self.slider_input(Button::ZL);
+        }
+    }
+    fn on_zr(&mut self) {
+        if matches!(self.submenu_type, SubMenuType::Slider) {
+            self.slider_input(Button::ZR);
+        }
+    }
 }
 
 impl<'a> SubMenu<'a> {
+    fn slider_input(&mut self, button: Button) {
+        self.slider
+            .as_mut()
+            .expect("No slider selected!")
+            .on_button(button);
+    }
+
+    /// Whether this submenu holds a value which is saved to JSON
+    pub fn is_serializable(&self) -> bool {
+        !matches!(self.submenu_type, SubMenuType::Action)
+    }
+
+    /// Number of nested submenus which hold a value, i.e. excluding Action submenus
+    pub fn serializable_len(&self) -> usize {
+        self.nested_iter().filter(|s| s.is_serializable()).count()
+    }
+
+    /// Whether this is a Nested submenu with anything in it to open
+    pub fn has_nested(&self) -> bool {
+        self.submenus.as_ref().is_some_and(|s| !s.is_empty())
+    }
+
+    /// The submenus one level down, or none if this isn't a Nested submenu
+    pub fn nested_iter(&self) -> impl Iterator<Item = &SubMenu<'a>> {
+        self.submenus.iter().flat_map(|submenus| submenus.iter())
+    }
+
+    /// A compact summary of the current value, for showing alongside the title
+    pub fn value_summary(&self) -> String {
+        match self.submenu_type {
+            SubMenuType::ToggleSingle => self
+                .toggles
+                .iter()
+                .find(|toggle| toggle.value > 0)
+                .map_or("None".to_string(), |toggle| toggle.title.to_string()),
+            SubMenuType::ToggleMultiple => {
+                let enabled: Vec<&str> = self
+                    .toggles
+                    .iter()
+                    .filter(|toggle| toggle.value > 0)
+                    .map(|toggle| toggle.title)
+                    .collect();
+                match enabled.len() {
+                    0 => "None".to_string(),
+                    n if n == self.toggles.len() => "All".to_string(),
+                    1 | 2 => enabled.join(", "),
+                    n => format!("{} selected", n),
+                }
+            }
+            SubMenuType::Slider => self
+                .slider
+                .as_ref()
+                .map_or(String::new(), |slider| slider.value_summary()),
+            SubMenuType::Nested => format!("{} settings", self.serializable_len()),
+            SubMenuType::Action | SubMenuType::None => String::new(),
+        }
+    }
+
     pub fn selected_toggle(&mut self) -> &mut Toggle<'a> {
         self.toggles.get_selected().expect("No toggle selected!")
     }
 
+    /// Checks that `update_from_value` would accept a JSON value, without changing anything
+    pub fn check_value(&self, value: &Value) -> Result<(), SettingsError> {
+        let invalid = || SettingsError::InvalidValue(self.id.to_string());
+        match self.submenu_type {
+            SubMenuType::Nested => {
+                let values = value.as_object().ok_or_else(invalid)?;
+                let submenus = self
+                    .submenus
+                    .iter()
+                    .flat_map(|s| s.items.iter().flatten().flatten());
+                for submenu in submenus {
+                    if let Some(val) = values.get(submenu.id) {
+                        submenu.check_value(val)?;
+                    }
+                }
+            }
+            SubMenuType::Slider => {
+                if let Some(slider) = self.slider.as_ref() {
+                    if !slider.check_value(value) {
+                        return Err(invalid());
+                    }
+                }
+            }
+            SubMenuType::Action | SubMenuType::None => {}
+            SubMenuType::ToggleSingle | SubMenuType::ToggleMultiple => {
+                let values: Vec<u8> =
+                    serde_json::from_value(value.clone()).map_err(|_| invalid())?;
+                let out_of_range = values.iter().enumerate().any(|(idx, value)| {
+                    self.toggles
+                        .get_by_idx(idx)
+                        .is_some_and(|toggle| *value > toggle.max)
+                });
+                if out_of_range {
+                    return Err(invalid());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Updates the submenu from a JSON value in the form produced by serializing it
+    pub fn update_from_value(&mut self, value: &Value) {
+        match self.submenu_type {
+            SubMenuType::Nested => {
+                let values = value
+                    .as_object()
+                    .expect("Nested submenu values must be a JSON object!");
+                let submenus = self
+                    .submenus
+                    .iter_mut()
+                    .flat_map(|s| s.iter_mut().flatten());
+                for submenu in submenus {
+                    if let Some(val) = values.get(submenu.id) {
+                        submenu.update_from_value(val);
+                    }
+                }
+            }
+            SubMenuType::Slider => {
+                // Slider values are parsed as the slider's own type, which may not be a u8
+                if let Some(slider) = self.slider.as_mut() {
+                    slider.update_from_value(value);
+                }
+            }
+            SubMenuType::Action | SubMenuType::None => {}
+            _ => {
+                let values: Vec<u8> =
+                    serde_json::from_value(value.clone()).expect("Could not parse the json!");
+                self.update_from_vec(values);
+            }
+        }
+    }
+
     pub fn update_from_vec(&mut self, values: Vec<u8>) {
         match self.submenu_type {
             SubMenuType::ToggleSingle | SubMenuType::ToggleMultiple => {
@@ -135,23 +288,25 @@ impl<'a> SubMenu<'a> {
                     2,
                     "Exactly two values need to be passed to submenu.set() for slider!"
                 );
-                if let Some(s) = self.slider {
-                    self.slider = Some(StatefulSlider {
-                        lower: values[0].into(),
-                        upper: values[1].into(),
-                        ..s
-                    });
+                if let Some(slider) = self.slider.as_mut() {
+                    slider.update_from_bytes(values[0], values[1]);
                 }
             }
+            SubMenuType::Nested => {}
+            SubMenuType::Action => {}
             SubMenuType::None => {}
         }
     }
 }
 
-#[derive(Clone, Copy, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SubMenuType {
     ToggleSingle,
     ToggleMultiple,
     Slider,
+    /// Contains another grid of submenus, one level further down the menu
+    Nested,
+    /// Runs a callback registered on the App instead of editing a value
+    Action,
     None,
 }
@@ -14,6 +14,15 @@ impl<'a> Tab<'a> {
     pub fn len(&self) -> usize {
         self.submenus.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.submenus.is_empty()
+    }
+
+    /// Number of submenus which hold a value, i.e. excluding Action submenus
+    pub fn serializable_len(&self) -> usize {
+        self.submenus.iter().filter(|s| s.is_serializable()).count()
+    }
 }
 
 impl<'a> Serialize for Tab<'a> {
@@ -21,8 +30,8 @@ impl<'a> Serialize for Tab<'a> {
     where
         S: Serializer,
     {
-        let mut map = serializer.serialize_map(Some(self.submenus.len()))?;
-        for submenu in self.submenus.as_vec().iter() {
+        let mut map = serializer.serialize_map(Some(self.serializable_len()))?;
+        for submenu in self.submenus.iter().filter(|s| s.is_serializable()) {
             map.serialize_entry(&submenu.title, &submenu)?;
         }
         map.end()
@@ -6,6 +6,9 @@ pub struct Toggle<'a> {
     pub title: &'a str,
     pub value: u8,
     pub max: u8,
+    /// Long-form help shown in the help overlay, which may contain markup.
+    /// If empty, the help for the submenu is shown instead.
+    pub help_text: &'a str,
 }
 
 impl<'a> Serialize for Toggle<'a> {
@@ -0,0 +1,312 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::Serialize;
+
+use crate::{
+    dispatch_slider, AnySlider, App, AppPage, NumericEntry, SliderMode, SliderState, SliderValue,
+    StatefulSlider, StatefulTable, SubMenuType,
+};
+
+/// Everything on the current screen, without any terminal specifics.
+/// The ratatui renderer draws from this, and other frontends such as the in-game overlay
+/// can serialize it or draw it their own way.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AppView {
+    pub tabs: Vec<TabView>,
+    pub selected_tab: usize,
+    /// Titles of the selected tab and each opened Nested submenu, outermost first
+    pub breadcrumbs: Vec<String>,
+    pub page: PageView,
+    pub footer: FooterView,
+    pub hints: Vec<HintView>,
+    /// The help overlay, if it is open
+    pub help: Option<HelpView>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TabView {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum PageView {
+    Submenus(GridView<SubmenuCell>),
+    Toggles {
+        submenu_type: SubMenuType,
+        grid: GridView<ToggleCell>,
+    },
+    Slider(SliderView),
+    Confirmation {
+        prompt: String,
+    },
+    Closed,
+}
+
+/// A grid of cells laid out in rows, of which the last may be partly filled
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GridView<C> {
+    pub rows: usize,
+    pub cols: usize,
+    /// The most columns the grid can be reflowed into, see `App::fit_columns`
+    pub preferred_cols: usize,
+    /// Row and column of the selected cell
+    pub selected: Option<(usize, usize)>,
+    /// The filled cells, row by row
+    pub cells: Vec<C>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SubmenuCell {
+    pub row: usize,
+    pub col: usize,
+    pub id: String,
+    pub title: String,
+    /// Short description of the current value, e.g. "On" or "40–100"
+    pub summary: String,
+    pub submenu_type: SubMenuType,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ToggleCell {
+    pub row: usize,
+    pub col: usize,
+    pub title: String,
+    pub value: u8,
+    pub max: u8,
+}
+
+/// A slider with its positions along the track, from 0.0 at the minimum to 1.0 at the maximum,
+/// and its labels already formatted
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SliderView {
+    pub title: String,
+    pub state: SliderState,
+    pub mode: SliderMode,
+    pub lower: f64,
+    pub upper: f64,
+    pub lower_label: String,
+    pub upper_label: String,
+    pub min_label: String,
+    pub max_label: String,
+    pub ticks: Vec<TickView>,
+    /// A value being typed in for the selected handle
+    pub entry: Option<EntryView>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TickView {
+    pub position: f64,
+    pub label: String,
+}
+
+/// A value being entered, as a row of glyphs of which one is under the cursor
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EntryView {
+    pub glyphs: Vec<EntryGlyph>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EntryGlyph {
+    pub text: String,
+    pub cursor: bool,
+}
+
+/// The line under the menu: the result of the last action until the player moves on,
+/// otherwise the focused submenu's help text
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum FooterView {
+    Message(String),
+    /// Help text, with markup
+    Help(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HintView {
+    /// The button as it should be shown for the current input source
+    pub label: String,
+    pub action: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HelpView {
+    pub title: String,
+    /// Help text, with markup
+    pub text: String,
+    pub scroll: u16,
+}
+
+impl<'a> App<'a> {
+    /// Describes the current screen
+    pub fn view(&self) -> AppView {
+        let tabs = self
+            .tabs
+            .iter()
+            .map(|tab| TabView {
+                id: tab.id.to_string(),
+                title: tab.title.to_string(),
+            })
+            .collect();
+        let page = match self.page {
+            AppPage::SUBMENU => {
+                PageView::Submenus(grid_view(self.current_submenu_table(), |row, col, s| {
+                    SubmenuCell {
+                        row,
+                        col,
+                        id: s.id.to_string(),
+                        title: s.title.to_string(),
+                        summary: s.value_summary(),
+                        submenu_type: s.submenu_type,
+                    }
+                }))
+            }
+            AppPage::TOGGLE => {
+                let submenu = self.current_submenu();
+                PageView::Toggles {
+                    submenu_type: submenu.submenu_type,
+                    grid: grid_view(&submenu.toggles, |row, col, t| ToggleCell {
+                        row,
+                        col,
+                        title: t.title.to_string(),
+                        value: t.value,
+                        max: t.max,
+                    }),
+                }
+            }
+            AppPage::SLIDER => {
+                let submenu = self.current_submenu();
+                let title = submenu.title;
+                let slider = submenu.slider.as_ref().expect("No slider selected!");
+                PageView::Slider(slider.view(title))
+            }
+            AppPage::CONFIRMATION => PageView::Confirmation {
+                prompt: self
+                    .pending_confirmation()
+                    .unwrap_or("Are you sure?")
+                    .to_string(),
+            },
+            AppPage::CLOSE => PageView::Closed,
+        };
+        let footer = match &self.action_message {
+            Some(message) => FooterView::Message(message.clone()),
+            None => FooterView::Help(self.current_submenu().help_text.to_string()),
+        };
+        let hints = self
+            .button_hints()
+            .iter()
+            .map(|hint| HintView {
+                label: self.button_label(hint.button),
+                action: hint.action.to_string(),
+            })
+            .collect();
+        let help = match self.focused_help() {
+            Some((title, text)) if self.help_open => Some(HelpView {
+                title: title.to_string(),
+                text: text.to_string(),
+                scroll: self.help_scroll,
+            }),
+            _ => None,
+        };
+        AppView {
+            tabs,
+            selected_tab: self.tabs.state.selected().unwrap_or(0),
+            breadcrumbs: self.breadcrumbs().iter().map(|c| c.to_string()).collect(),
+            page,
+            footer,
+            hints,
+            help,
+        }
+    }
+}
+
+/// Describes the filled cells of a table
+fn grid_view<T, C, F>(table: &StatefulTable<T>, cell: F) -> GridView<C>
+where
+    T: Clone + Serialize,
+    F: Fn(usize, usize, &T) -> C,
+{
+    let mut cells = vec![];
+    for (row_idx, row) in table.items.iter().enumerate() {
+        for (col_idx, item) in row.iter().enumerate() {
+            if let Some(item) = item {
+                cells.push(cell(row_idx, col_idx, item));
+            }
+        }
+    }
+    GridView {
+        rows: table.rows,
+        cols: table.cols,
+        preferred_cols: table.preferred_cols,
+        selected: table.state.selected_row().zip(table.state.selected_col()),
+        cells,
+    }
+}
+
+impl AnySlider {
+    pub fn view(&self, title: &str) -> SliderView {
+        dispatch_slider!(self, slider => slider.view(title))
+    }
+}
+
+impl<T: SliderValue> StatefulSlider<T> {
+    /// Describes the slider, titled with its mode if the handles don't move independently
+    pub fn view(&self, title: &str) -> SliderView {
+        let title = match self.mode {
+            SliderMode::Clamp => title.to_string(),
+            SliderMode::Push => format!("{} (Push)", title),
+            SliderMode::LockedWidth => format!("{} (Locked Width)", title),
+        };
+        SliderView {
+            title,
+            state: self.state,
+            mode: self.mode,
+            lower: self.fraction(self.lower),
+            upper: self.fraction(self.upper),
+            lower_label: self.format_value(self.lower),
+            upper_label: self.format_value(self.upper),
+            min_label: self.format_value(self.min),
+            max_label: self.format_value(self.max),
+            ticks: self
+                .format
+                .ticks
+                .iter()
+                .map(|tick| TickView {
+                    position: self.fraction(tick.value),
+                    label: tick.label.to_string(),
+                })
+                .collect(),
+            entry: self.entry.as_ref().map(entry_view),
+        }
+    }
+}
+
+fn entry_view(entry: &NumericEntry) -> EntryView {
+    let glyph = |text: String, slot: Option<usize>| EntryGlyph {
+        text,
+        cursor: slot == Some(entry.cursor),
+    };
+    let mut glyphs = vec![];
+    let mut slot = 0;
+    if entry.has_sign {
+        let sign = if entry.negative { "-" } else { "+" };
+        glyphs.push(glyph(sign.to_string(), Some(slot)));
+        slot += 1;
+    }
+    for (idx, digit) in entry.digits().iter().enumerate() {
+        // The decimal point isn't a slot, so the cursor never lands on it
+        if entry.decimals > 0 && idx == entry.len - entry.decimals {
+            glyphs.push(glyph(".".to_string(), None));
+        }
+        glyphs.push(glyph(digit.to_string(), Some(slot)));
+        slot += 1;
+    }
+    EntryView {
+        glyphs,
+        error: entry.error.map(|error| error.to_string()),
+    }
+}
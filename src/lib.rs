@@ -1,10 +1,21 @@
+//! The menu model builds with only `alloc`, for consoles without std.
+//! The terminal UI, drawn with ratatui, needs the `has_terminal` feature.
+//! The C interface is in the `training_mod_tui_2_ffi` crate, in `ffi/`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod containers;
 pub use containers::*;
 mod structures;
 pub use structures::*;
 
+#[cfg(feature = "has_terminal")]
 pub mod ui;
+#[cfg(feature = "has_terminal")]
 pub use crate::ui::*;
 
+/// Default grid size for tabs and submenus. Each table can be created with its own size,
+/// and can be reflowed into fewer columns with `App::fit_columns` when the terminal is too narrow.
 pub const NX_SUBMENU_ROWS: usize = 8;
 pub const NX_SUBMENU_COLUMNS: usize = 4;
@@ -1,5 +1,8 @@
 use crossterm::{
-    event::{self, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -7,24 +10,103 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use std::error::Error;
 use std::{
+    cell::Cell,
     io,
+    rc::Rc,
     time::{Duration, Instant},
 };
 
 use training_mod_tui_2::{
-    App, AppPage, InputControl, StatefulList, StatefulSlider, StatefulTable, SubMenu, SubMenuType,
-    Tab, Toggle, NX_SUBMENU_COLUMNS, NX_SUBMENU_ROWS,
+    App, AppPage, AutoRepeat, Button, ButtonEvent, EntryKey, InputSource, Key, KeyAction, KeyChord,
+    KeyRepeatFilter, Keymap, MouseInput, SliderFormat, SliderTick, StatefulList, StatefulSlider,
+    StatefulTable, SubMenu, SubMenuType, Tab, Theme, Toggle, UiLayout, UiState, ACTION_CYCLE_THEME,
+    ACTION_RESET_DEFAULTS, ACTION_SAVE_DEFAULTS, ASCII_SYMBOLS, NX_SUBMENU_COLUMNS,
+    NX_SUBMENU_ROWS,
 };
 
+// Terminals don't report key releases, only repeated presses while a key is held down.
+// A direction counts as held until no press has come for a while: long enough for the
+// terminal's key repeat to start, then only a little longer than its repeat interval.
+// Presses further apart than the repeat interval are separate taps.
+const KEY_FIRST_REPEAT_TIMEOUT: Duration = Duration::from_millis(600);
+const KEY_REPEAT_TIMEOUT: Duration = Duration::from_millis(100);
+
 fn main() -> Result<(), Box<dyn Error>> {
     let mut app = create_app();
+    // The theme is drawn from the UiState, so the action only asks the render loop to switch
+    let cycle_theme = Rc::new(Cell::new(false));
+    let requested = Rc::clone(&cycle_theme);
+    app.register_action(
+        ACTION_CYCLE_THEME,
+        None,
+        Box::new(move |_| {
+            requested.set(true);
+            None
+        }),
+    );
+    let mut ui_state = UiState::default();
+    // A theme file can be passed with `--theme <path>`
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(idx) = args.iter().position(|arg| arg == "--theme") {
+        let path = args.get(idx + 1).ok_or("--theme requires a path")?;
+        ui_state.add_theme(Theme::load(path)?);
+    }
+    // Terminals without Unicode glyphs can use `--ascii`
+    if args.iter().any(|arg| arg == "--ascii") {
+        for theme in ui_state.themes.iter_mut() {
+            theme.symbols = ASCII_SYMBOLS;
+        }
+    }
     let json = "{\"Menu Open Start Press\":[1,0],\"Dmg Range (CPU)\":[40,100]}";
-    app.update_from_json(json);
+    app.update_from_json(json)?;
+    // `--session <path>` reopens the menu where it was last closed
+    let session_path = match args.iter().position(|arg| arg == "--session") {
+        Some(idx) => Some(args.get(idx + 1).ok_or("--session requires a path")?),
+        None => None,
+    };
+    if let Some(path) = session_path {
+        if let Ok(session) = std::fs::read_to_string(path) {
+            // A corrupt session only loses the menu position, so start from the top instead
+            if let Err(err) = app.restore_session_from_json(&session) {
+                eprintln!("Ignoring the session in {}: {}", path, err);
+            }
+        }
+    }
+    // `--keymap <preset or path>` picks a preset such as "vim", or loads a keymap file
+    let keymap = match args.iter().position(|arg| arg == "--keymap") {
+        Some(idx) => {
+            let name = args
+                .get(idx + 1)
+                .ok_or("--keymap requires a preset or path")?;
+            match Keymap::preset(name) {
+                Some(keymap) => keymap,
+                None => Keymap::load(name)?,
+            }
+        }
+        None => Keymap::default(),
+    };
+    // Button hints show the bound keys instead of controller buttons
+    app.input_source = InputSource::Keyboard(keymap.labels());
     let mut terminal = setup_terminal()?;
 
-    let tick_rate = Duration::from_millis(250);
-    let res = run_app(&mut terminal, app, tick_rate);
+    let tick_rate = Duration::from_millis(50);
+    app.auto_repeat = AutoRepeat {
+        // A tap is only known not to be a hold once the first repeat timeout has passed
+        initial_delay: (KEY_FIRST_REPEAT_TIMEOUT.as_millis() / tick_rate.as_millis()) as u32 + 1,
+        ..AutoRepeat::for_tick_length(tick_rate)
+    };
+    let res = run_app(
+        &mut terminal,
+        &mut app,
+        &mut ui_state,
+        &cycle_theme,
+        &keymap,
+        tick_rate,
+    );
     restore_terminal(terminal)?;
+    if let Some(path) = session_path {
+        std::fs::write(path, app.session_to_json())?;
+    }
 
     if let Err(err) = res {
         println!("Error: {:?}", err)
@@ -48,103 +130,139 @@ fn restore_terminal(
     mut terminal: Terminal<CrosstermBackend<io::Stdout>>,
 ) -> Result<(), Box<dyn Error>> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
 
 pub fn create_app<'a>() -> App<'a> {
     let mut app = App::new();
-    let mut button_tab_submenus: Vec<SubMenu> = Vec::new();
-    button_tab_submenus.push(SubMenu {
-        title: "Menu Open Start Press",
-        id: "menu_open_start_press",
-        help_text: "Menu Open Start Press: Should pressing start open the menu?",
-        submenu_type: SubMenuType::ToggleSingle,
-        toggles: new_toggle_table(new_on_off()),
-        slider: None,
-    });
-    button_tab_submenus.push(SubMenu {
-        title: "Save State Save",
-        id: "save_state_save",
-        help_text: "Save State Save: Hold any one button and press the others to trigger",
-        submenu_type: SubMenuType::ToggleMultiple,
-        toggles: new_toggle_table(new_button_combo()),
-        slider: None,
-    });
-    button_tab_submenus.push(SubMenu {
-        title: "Save State Load",
-        id: "save_state_load",
-        help_text: "Save State Load: Hold any one button and press the others to trigger",
-        submenu_type: SubMenuType::ToggleMultiple,
-        toggles: new_toggle_table(new_button_combo()),
-        slider: None,
-    });
-    button_tab_submenus.push(SubMenu {
-        title: "Input Record",
-        id: "input_record",
-        help_text: "Input Record: Hold any one button and press the others to trigger",
-        submenu_type: SubMenuType::ToggleMultiple,
-        toggles: new_toggle_table(new_button_combo()),
-        slider: None,
-    });
-    button_tab_submenus.push(SubMenu {
-        title: "Input Playback",
-        id: "input_playback",
-        help_text: "Input Playback: Hold any one button and press the others to trigger",
-        submenu_type: SubMenuType::ToggleMultiple,
-        toggles: new_toggle_table(new_button_combo()),
-        slider: None,
-    });
+    let button_tab_submenus: Vec<SubMenu> = vec![
+        SubMenu {
+            title: "Menu Open Start Press",
+            id: "menu_open_start_press",
+            help_text: "Menu Open Start Press: Should pressing start open the menu?",
+            submenu_type: SubMenuType::ToggleSingle,
+            toggles: new_toggle_table(new_on_off()),
+            slider: None,
+            submenus: None,
+        },
+        SubMenu {
+            title: "Save State Save",
+            id: "save_state_save",
+            help_text: "Save State Save: Hold any one button and press the others to trigger",
+            submenu_type: SubMenuType::ToggleMultiple,
+            toggles: new_toggle_table(new_button_combo()),
+            slider: None,
+            submenus: None,
+        },
+        SubMenu {
+            title: "Save State Load",
+            id: "save_state_load",
+            help_text: "Save State Load: Hold any one button and press the others to trigger",
+            submenu_type: SubMenuType::ToggleMultiple,
+            toggles: new_toggle_table(new_button_combo()),
+            slider: None,
+            submenus: None,
+        },
+        SubMenu {
+            title: "Input Record",
+            id: "input_record",
+            help_text: "Input Record: Hold any one button and press the others to trigger",
+            submenu_type: SubMenuType::ToggleMultiple,
+            toggles: new_toggle_table(new_button_combo()),
+            slider: None,
+            submenus: None,
+        },
+        SubMenu {
+            title: "Input Playback",
+            id: "input_playback",
+            help_text: "Input Playback: Hold any one button and press the others to trigger",
+            submenu_type: SubMenuType::ToggleMultiple,
+            toggles: new_toggle_table(new_button_combo()),
+            slider: None,
+            submenus: None,
+        },
+    ];
 
-    let button_tab = Tab {
-        id: "button",
-        title: "Button Config",
-        submenus: StatefulTable::with_items(
-            NX_SUBMENU_ROWS,
-            NX_SUBMENU_COLUMNS,
-            button_tab_submenus.clone(),
-        ),
-    };
-    let button_tab_2 = Tab {
-        id: "button",
-        title: "Button Config 2",
-        submenus: StatefulTable::with_items(
-            NX_SUBMENU_ROWS,
-            NX_SUBMENU_COLUMNS,
-            button_tab_submenus.clone(),
-        ),
-    };
-    let button_tab_3 = Tab {
-        id: "button",
-        title: "Button Config 3",
-        submenus: StatefulTable::with_items(
-            NX_SUBMENU_ROWS,
-            NX_SUBMENU_COLUMNS,
-            button_tab_submenus.clone(),
-        ),
-    };
-    let button_tab_4 = Tab {
-        id: "button",
-        title: "Button Config 4",
+    // The other Button Config tabs hold the same settings, under ids and titles of their own
+    let button_tab_copy = |id: &'a str, title: &'a str, names: [(&'a str, &'a str); 5]| Tab {
+        id,
+        title,
         submenus: StatefulTable::with_items(
             NX_SUBMENU_ROWS,
             NX_SUBMENU_COLUMNS,
-            button_tab_submenus.clone(),
+            button_tab_submenus
+                .iter()
+                .zip(names)
+                .map(|(submenu, (title, id))| SubMenu {
+                    title,
+                    id,
+                    ..submenu.clone()
+                })
+                .collect(),
         ),
     };
-    let button_tab_5 = Tab {
+    let button_tab_2 = button_tab_copy(
+        "button_2",
+        "Button Config 2",
+        [
+            ("Menu Open Start Press 2", "menu_open_start_press_2"),
+            ("Save State Save 2", "save_state_save_2"),
+            ("Save State Load 2", "save_state_load_2"),
+            ("Input Record 2", "input_record_2"),
+            ("Input Playback 2", "input_playback_2"),
+        ],
+    );
+    let button_tab_3 = button_tab_copy(
+        "button_3",
+        "Button Config 3",
+        [
+            ("Menu Open Start Press 3", "menu_open_start_press_3"),
+            ("Save State Save 3", "save_state_save_3"),
+            ("Save State Load 3", "save_state_load_3"),
+            ("Input Record 3", "input_record_3"),
+            ("Input Playback 3", "input_playback_3"),
+        ],
+    );
+    let button_tab_4 = button_tab_copy(
+        "button_4",
+        "Button Config 4",
+        [
+            ("Menu Open Start Press 4", "menu_open_start_press_4"),
+            ("Save State Save 4", "save_state_save_4"),
+            ("Save State Load 4", "save_state_load_4"),
+            ("Input Record 4", "input_record_4"),
+            ("Input Playback 4", "input_playback_4"),
+        ],
+    );
+    let button_tab_5 = button_tab_copy(
+        "button_5",
+        "Button Config 5",
+        [
+            ("Menu Open Start Press 5", "menu_open_start_press_5"),
+            ("Save State Save 5", "save_state_save_5"),
+            ("Save State Load 5", "save_state_load_5"),
+            ("Input Record 5", "input_record_5"),
+            ("Input Playback 5", "input_playback_5"),
+        ],
+    );
+    let button_tab = Tab {
         id: "button",
-        title: "Button Config 5",
+        title: "Button Config",
         submenus: StatefulTable::with_items(
             NX_SUBMENU_ROWS,
             NX_SUBMENU_COLUMNS,
-            button_tab_submenus.clone(),
+            button_tab_submenus,
         ),
     };
 
-    let mut save_state_tab_submenus: Vec<SubMenu> = Vec::new();
-    save_state_tab_submenus.push(SubMenu {
+    let mut save_state_tab_submenus: Vec<SubMenu> = vec![
+        SubMenu {
         title: "Mirroring",
         id: "save_state_mirroring",
         help_text:
@@ -152,31 +270,127 @@ pub fn create_app<'a>() -> App<'a> {
         submenu_type: SubMenuType::ToggleSingle,
         toggles: new_toggle_table(new_on_off()),
         slider: None,
-    });
-    save_state_tab_submenus.push(SubMenu {
+        submenus: None,
+        },
+        SubMenu {
         title: "Auto Save States",
         id: "save_state_autoload",
         help_text: "Auto Save States: Load save state when any fighter dies",
         submenu_type: SubMenuType::ToggleSingle,
         toggles: new_toggle_table(new_on_off()),
         slider: None,
-    });
-    save_state_tab_submenus.push(SubMenu {
+        submenus: None,
+        },
+        SubMenu {
         title: "Dmg Range (CPU)",
         id: "save_damage_limits_cpu",
-        help_text: "Limits on random damage to apply to the CPU when loading a save state",
+        help_text: "Dmg Range (CPU): Limits on random damage to apply to the CPU when loading a save state\n\
+            \n\
+            - <A> selects a handle and <B> deselects it\n\
+            - [yellow]Left/Right[/] move the handle by 1, [yellow]Up/Down[/] by 10\n\
+            - <X> types in an exact value\n\
+            - <ZL> and <ZR> jump between **preset** damage values\n\
+            - [yellow]Left/Right[/] also hover **both** handles, to move the whole range\n\
+            - <L> switches between clamp, push and locked width modes",
         submenu_type: SubMenuType::Slider,
         toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
-        slider: Some(StatefulSlider::new()),
+        slider: Some(StatefulSlider {
+            format: SliderFormat {
+                unit: "%".to_string(),
+                ticks: vec![
+                    SliderTick {
+                        value: 0,
+                        label: "Fresh".to_string(),
+                    },
+                    SliderTick {
+                        value: 100,
+                        label: "Kill %".to_string(),
+                    },
+                ],
+                ..SliderFormat::default()
+            },
+            presets: vec![0, 30, 60, 90, 120, 150],
+            range_handle: true,
+            ..StatefulSlider::new()
+        }
+        .into()),
+        submenus: None,
+        },
+    ];
+    let save_state_slot_submenus: Vec<SubMenu> = vec![
+        SubMenu {
+            title: "Slot 1 Enabled",
+            id: "save_state_slot_1",
+            help_text: "Slot 1 Enabled: Include the first save state slot when loading randomly",
+            submenu_type: SubMenuType::ToggleSingle,
+            toggles: new_toggle_table(new_on_off()),
+            slider: None,
+            submenus: None,
+        },
+        SubMenu {
+            title: "Slot 2 Enabled",
+            id: "save_state_slot_2",
+            help_text: "Slot 2 Enabled: Include the second save state slot when loading randomly",
+            submenu_type: SubMenuType::ToggleSingle,
+            toggles: new_toggle_table(new_on_off()),
+            slider: None,
+            submenus: None,
+        },
+    ];
+    save_state_tab_submenus.push(SubMenu {
+        title: "Save State Slots",
+        id: "save_state_slots",
+        help_text: "Save State Slots: Choose which save state slots are used",
+        submenu_type: SubMenuType::Nested,
+        toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
+        slider: None,
+        submenus: Some(StatefulTable::with_items(
+            NX_SUBMENU_ROWS,
+            NX_SUBMENU_COLUMNS,
+            save_state_slot_submenus,
+        )),
+    });
+    save_state_tab_submenus.push(SubMenu {
+        title: "Clear Recording",
+        id: "clear_recorded_inputs",
+        help_text: "Clear Recording: Erase all recorded inputs",
+        submenu_type: SubMenuType::Action,
+        toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
+        slider: None,
+        submenus: None,
+    });
+    save_state_tab_submenus.push(SubMenu {
+        title: "Save Defaults",
+        id: ACTION_SAVE_DEFAULTS,
+        help_text: "Save Defaults: Save the current settings as the defaults",
+        submenu_type: SubMenuType::Action,
+        toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
+        slider: None,
+        submenus: None,
+    });
+    save_state_tab_submenus.push(SubMenu {
+        title: "Reset All",
+        id: ACTION_RESET_DEFAULTS,
+        help_text: "Reset All: Reset all settings to the saved defaults",
+        submenu_type: SubMenuType::Action,
+        toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
+        slider: None,
+        submenus: None,
+    });
+    save_state_tab_submenus.push(SubMenu {
+        title: "Menu Theme",
+        id: ACTION_CYCLE_THEME,
+        help_text: "Menu Theme: Switch to the next colour theme",
+        submenu_type: SubMenuType::Action,
+        toggles: StatefulTable::new(NX_SUBMENU_ROWS, NX_SUBMENU_COLUMNS),
+        slider: None,
+        submenus: None,
     });
     let save_states_tab = Tab {
         id: "save_state",
         title: "Save States",
-        submenus: StatefulTable::with_items(
-            NX_SUBMENU_ROWS,
-            NX_SUBMENU_COLUMNS,
-            save_state_tab_submenus,
-        ),
+        // Fewer, wider columns leave room for the longer titles on this tab
+        submenus: StatefulTable::with_items(NX_SUBMENU_ROWS, 3, save_state_tab_submenus),
     };
 
     app.tabs = StatefulList::with_items(vec![
@@ -187,113 +401,226 @@ pub fn create_app<'a>() -> App<'a> {
         button_tab_5,
         save_states_tab,
     ]);
+    app.register_action(
+        "clear_recorded_inputs",
+        Some("Clear all recorded inputs?"),
+        Box::new(|_: &mut App| Some("Recorded inputs cleared".to_string())),
+    );
     app
 }
 
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
-    mut app: training_mod_tui_2::App,
+    app: &mut training_mod_tui_2::App,
+    ui_state: &mut UiState,
+    cycle_theme: &Cell<bool>,
+    keymap: &Keymap,
     tick_rate: Duration,
 ) -> io::Result<String> {
     let mut last_tick = Instant::now();
+    // Where everything was drawn last frame, for mapping mouse positions
+    let mut layout = UiLayout::default();
+    let mut show_bindings = false;
+    // Directions which are down, going by the terminal's key repeats
+    let started = Instant::now();
+    let mut held_keys = KeyRepeatFilter::new(KEY_FIRST_REPEAT_TIMEOUT, KEY_REPEAT_TIMEOUT);
     loop {
-        terminal.draw(|f| training_mod_tui_2::render_ui(f, &mut app))?;
+        if cycle_theme.take() {
+            ui_state.cycle_theme();
+            app.action_message = Some(format!("Theme: {}", ui_state.theme().name));
+        }
+        terminal.draw(|f| {
+            layout = training_mod_tui_2::render_ui(f, app, ui_state);
+            if show_bindings {
+                training_mod_tui_2::render_keymap_overlay(f, keymap, ui_state.theme());
+            }
+        })?;
         if app.page == AppPage::CLOSE {
             return Ok(app.to_json());
         }
+        // Redraw straight away if the grid had to be reflowed to fit the terminal
+        if app.fit_columns(ui_state.columns) {
+            continue;
+        }
+        // Don't let the help scroll on past the end of the text while a key is held
+        if app.help_open {
+            app.help_scroll = ui_state.help_scroll;
+        }
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => app.page = AppPage::CLOSE,
-                    KeyCode::Char('a') => app.on_a(),
-                    KeyCode::Char('b') => app.on_b(),
-                    KeyCode::Char('x') => app.on_x(),
-                    KeyCode::Char('y') => app.on_y(),
-                    KeyCode::Char('o') => app.on_zl(),
-                    KeyCode::Char('p') => app.on_zr(),
-                    KeyCode::Char('l') => app.on_l(),
-                    KeyCode::Char('r') => app.on_r(),
-                    KeyCode::Left => app.on_left(),
-                    KeyCode::Right => app.on_right(),
-                    KeyCode::Down => app.on_down(),
-                    KeyCode::Up => app.on_up(),
-                    KeyCode::Enter => app.on_a(),
-                    KeyCode::Backspace => app.on_b(),
-                    _ => {}
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                let input = match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => Some(MouseInput::Press {
+                        x: mouse.column,
+                        y: mouse.row,
+                    }),
+                    MouseEventKind::Drag(MouseButton::Left) => Some(MouseInput::Drag {
+                        x: mouse.column,
+                        y: mouse.row,
+                    }),
+                    MouseEventKind::Up(MouseButton::Left) => Some(MouseInput::Release),
+                    MouseEventKind::ScrollUp => Some(MouseInput::ScrollUp),
+                    MouseEventKind::ScrollDown => Some(MouseInput::ScrollDown),
+                    _ => None,
+                };
+                if let Some(input) = input {
+                    app.handle_mouse(&layout, ui_state, input);
+                }
+            }
+            if let Event::Key(key) = event {
+                // While a slider value is being entered, digits, '-' and Backspace type into it
+                let entry_key = match key.code {
+                    KeyCode::Char(c) if c.is_ascii_digit() => Some(EntryKey::Digit(c as u8 - b'0')),
+                    KeyCode::Char('-') => Some(EntryKey::Minus),
+                    KeyCode::Backspace => Some(EntryKey::Backspace),
+                    _ => None,
+                };
+                if let Some(entry_key) = entry_key {
+                    if app.on_entry_key(entry_key) {
+                        continue;
+                    }
+                }
+                let action = key_chord(key).and_then(|chord| keymap.action(chord));
+                if show_bindings {
+                    // Any key which would go back closes the list of bindings
+                    show_bindings = !matches!(
+                        action,
+                        Some(KeyAction::ShowBindings) | Some(KeyAction::Press(Button::B))
+                    );
+                    continue;
+                }
+                match action {
+                    Some(KeyAction::Press(button)) if button.is_direction() => {
+                        // The terminal's own repeats keep the direction held,
+                        // and the app repeats it at its own rate
+                        for event in held_keys.press(button, started.elapsed()) {
+                            app.handle_input(button, *event);
+                        }
+                    }
+                    Some(KeyAction::Press(button)) => {
+                        app.handle_input(button, ButtonEvent::Press);
+                        app.handle_input(button, ButtonEvent::Release);
+                    }
+                    Some(KeyAction::ShowBindings) => show_bindings = true,
+                    None => {}
                 }
             }
         }
         if last_tick.elapsed() >= tick_rate {
             last_tick = Instant::now();
+            for button in held_keys.release_expired(started.elapsed()) {
+                app.handle_input(button, ButtonEvent::Release);
+            }
+            app.tick();
         }
     }
 }
 
+/// The keymap's view of a key press, or None for keys it can't bind
+fn key_chord(event: KeyEvent) -> Option<KeyChord> {
+    let key = match event.code {
+        KeyCode::Char(c) => Key::Char(c),
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Esc => Key::Esc,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::F(number) => Key::F(number),
+        _ => return None,
+    };
+    Some(KeyChord {
+        key,
+        ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        alt: event.modifiers.contains(KeyModifiers::ALT),
+        // Shifted characters arrive as their upper-case characters
+        shift: !matches!(key, Key::Char(_)) && event.modifiers.contains(KeyModifiers::SHIFT),
+    })
+}
+
 fn new_button_combo<'a>() -> Vec<Toggle<'a>> {
     let a_button = Toggle {
         title: "A Button",
         value: 0,
         max: 1,
+        help_text: "Include <A> in the combo",
     };
     let b_button = Toggle {
         title: "B Button",
         value: 0,
         max: 1,
+        help_text: "Include <B> in the combo",
     };
     let x_button = Toggle {
         title: "X Button",
         value: 0,
         max: 1,
+        help_text: "Include <X> in the combo",
     };
     let y_button = Toggle {
         title: "Y Button",
         value: 0,
         max: 1,
+        help_text: "Include <Y> in the combo",
     };
     let l_button = Toggle {
         title: "L Button",
         value: 0,
         max: 1,
+        help_text: "Include <L> in the combo",
     };
     let r_button = Toggle {
         title: "R Button",
         value: 0,
         max: 1,
+        help_text: "Include <R> in the combo",
     };
     let zl_button = Toggle {
         title: "ZL Button",
         value: 0,
         max: 1,
+        help_text: "Include <ZL> in the combo",
     };
     let zr_button = Toggle {
         title: "ZR Button",
         value: 0,
         max: 1,
+        help_text: "Include <ZR> in the combo",
     };
     let dpad_up_button = Toggle {
         title: "Dpad Up Button",
         value: 0,
         max: 1,
+        help_text: "Include **Dpad Up** in the combo",
     };
     let dpad_down_button = Toggle {
         title: "Dpad Down Button",
         value: 0,
         max: 1,
+        help_text: "Include **Dpad Down** in the combo",
     };
     let dpad_left_button = Toggle {
         title: "Dpad Left Button",
         value: 0,
         max: 1,
+        help_text: "Include **Dpad Left** in the combo",
     };
     let dpad_right_button = Toggle {
         title: "Dpad Right Button",
         value: 0,
         max: 1,
+        help_text: "Include **Dpad Right** in the combo",
     };
     vec![
         a_button,
@@ -316,11 +643,13 @@ fn new_on_off<'a>() -> Vec<Toggle<'a>> {
         title: "True",
         value: 0,
         max: 1,
+        help_text: "",
     };
     let false_toggle = Toggle {
         title: "False",
         value: 0,
         max: 1,
+        help_text: "",
     };
     vec![true_toggle, false_toggle]
 }
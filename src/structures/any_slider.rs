@@ -0,0 +1,404 @@
+use alloc::{format, string::String};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{
+    Button, NumericEntry, SliderEntryError, SliderMode, SliderState, SliderValue, StatefulSlider,
+};
+
+/// A StatefulSlider over any of the supported value types, so that submenus can hold
+/// sliders of different types. Each serializes as `[lower, upper]` in its own type.
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnySlider {
+    U8(StatefulSlider<u8>),
+    U16(StatefulSlider<u16>),
+    U32(StatefulSlider<u32>),
+    U64(StatefulSlider<u64>),
+    Usize(StatefulSlider<usize>),
+    I8(StatefulSlider<i8>),
+    I16(StatefulSlider<i16>),
+    I32(StatefulSlider<i32>),
+    I64(StatefulSlider<i64>),
+    Isize(StatefulSlider<isize>),
+    F32(StatefulSlider<f32>),
+    F64(StatefulSlider<f64>),
+}
+
+/// Something a button does to a slider, depending on its state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliderAction {
+    SelectHandle,
+    DeselectHandle,
+    BeginEntry,
+    ConfirmEntry,
+    CancelEntry,
+    /// Changes the entry's digit under the cursor
+    DialUp,
+    DialDown,
+    CursorLeft,
+    CursorRight,
+    Increment,
+    Decrement,
+    IncrementFast,
+    DecrementFast,
+    SwitchHover,
+    CycleMode,
+    PreviousPreset,
+    NextPreset,
+}
+
+impl SliderAction {
+    /// How the action is described in button hints
+    pub fn description(&self) -> &'static str {
+        match self {
+            SliderAction::SelectHandle => "Select handle",
+            SliderAction::DeselectHandle => "Deselect handle",
+            SliderAction::BeginEntry => "Type value",
+            SliderAction::ConfirmEntry => "Confirm value",
+            SliderAction::CancelEntry => "Cancel",
+            SliderAction::DialUp => "Digit up",
+            SliderAction::DialDown => "Digit down",
+            SliderAction::CursorLeft => "Prev digit",
+            SliderAction::CursorRight => "Next digit",
+            SliderAction::Increment => "Increase",
+            SliderAction::Decrement => "Decrease",
+            SliderAction::IncrementFast => "Increase fast",
+            SliderAction::DecrementFast => "Decrease fast",
+            SliderAction::SwitchHover => "Switch handle",
+            SliderAction::CycleMode => "Change mode",
+            SliderAction::PreviousPreset => "Prev preset",
+            SliderAction::NextPreset => "Next preset",
+        }
+    }
+}
+
+/// The value type of an AnySlider, e.g. `"u8"` or `"f32"` in menu definitions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SliderType {
+    U8,
+    U16,
+    #[default]
+    U32,
+    U64,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    F32,
+    F64,
+}
+
+/// Runs the same code on whichever slider an AnySlider holds
+macro_rules! dispatch_slider {
+    ($any:expr, $slider:ident => $body:expr) => {
+        match $any {
+            AnySlider::U8($slider) => $body,
+            AnySlider::U16($slider) => $body,
+            AnySlider::U32($slider) => $body,
+            AnySlider::U64($slider) => $body,
+            AnySlider::Usize($slider) => $body,
+            AnySlider::I8($slider) => $body,
+            AnySlider::I16($slider) => $body,
+            AnySlider::I32($slider) => $body,
+            AnySlider::I64($slider) => $body,
+            AnySlider::Isize($slider) => $body,
+            AnySlider::F32($slider) => $body,
+            AnySlider::F64($slider) => $body,
+        }
+    };
+}
+pub(crate) use dispatch_slider;
+
+/// Slider value types which have a variant in AnySlider
+pub trait AnySliderValue: SliderValue {
+    const SLIDER_TYPE: SliderType;
+
+    fn wrap(slider: StatefulSlider<Self>) -> AnySlider;
+    fn unwrap_ref(slider: &AnySlider) -> Option<&StatefulSlider<Self>>;
+    fn unwrap_mut(slider: &mut AnySlider) -> Option<&mut StatefulSlider<Self>>;
+}
+
+macro_rules! impl_any_slider_value {
+    ($($t:ty => $variant:ident),*) => {
+        $(
+            impl AnySliderValue for $t {
+                const SLIDER_TYPE: SliderType = SliderType::$variant;
+
+                fn wrap(slider: StatefulSlider<Self>) -> AnySlider {
+                    AnySlider::$variant(slider)
+                }
+                fn unwrap_ref(slider: &AnySlider) -> Option<&StatefulSlider<Self>> {
+                    match slider {
+                        AnySlider::$variant(slider) => Some(slider),
+                        _ => None,
+                    }
+                }
+                fn unwrap_mut(slider: &mut AnySlider) -> Option<&mut StatefulSlider<Self>> {
+                    match slider {
+                        AnySlider::$variant(slider) => Some(slider),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_any_slider_value!(
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, usize => Usize,
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, isize => Isize,
+    f32 => F32, f64 => F64
+);
+
+impl<T: AnySliderValue> From<StatefulSlider<T>> for AnySlider {
+    fn from(slider: StatefulSlider<T>) -> AnySlider {
+        T::wrap(slider)
+    }
+}
+
+impl AnySlider {
+    pub fn slider_type(&self) -> SliderType {
+        fn slider_type<T: AnySliderValue>(_: &StatefulSlider<T>) -> SliderType {
+            T::SLIDER_TYPE
+        }
+        dispatch_slider!(self, slider => slider_type(slider))
+    }
+
+    /// The slider, if it holds values of type T
+    pub fn get<T: AnySliderValue>(&self) -> Option<&StatefulSlider<T>> {
+        T::unwrap_ref(self)
+    }
+
+    /// The slider, if it holds values of type T
+    pub fn get_mut<T: AnySliderValue>(&mut self) -> Option<&mut StatefulSlider<T>> {
+        T::unwrap_mut(self)
+    }
+
+    pub fn state(&self) -> SliderState {
+        dispatch_slider!(self, slider => slider.state)
+    }
+
+    pub fn mode(&self) -> SliderMode {
+        dispatch_slider!(self, slider => slider.mode)
+    }
+
+    pub fn cycle_mode(&mut self) {
+        dispatch_slider!(self, slider => slider.cycle_mode())
+    }
+
+    /// What a button does to the slider in its current state, if anything.
+    /// The slider page's input and its button hints both come from this.
+    pub fn action(&self, button: Button) -> Option<SliderAction> {
+        let entering = self.is_entering();
+        let selected = self.is_handle_selected();
+        let hovering = !selected && self.state() != SliderState::None;
+        let one_handle = self.is_single_handle_selected();
+        match button {
+            Button::A if entering => Some(SliderAction::ConfirmEntry),
+            Button::A if selected => Some(SliderAction::DeselectHandle),
+            Button::A if hovering => Some(SliderAction::SelectHandle),
+            Button::B if entering => Some(SliderAction::CancelEntry),
+            Button::B if selected => Some(SliderAction::DeselectHandle),
+            Button::X if !entering && one_handle => Some(SliderAction::BeginEntry),
+            Button::Up if entering => Some(SliderAction::DialUp),
+            Button::Up if selected => Some(SliderAction::IncrementFast),
+            Button::Down if entering => Some(SliderAction::DialDown),
+            Button::Down if selected => Some(SliderAction::DecrementFast),
+            Button::Left if entering => Some(SliderAction::CursorLeft),
+            Button::Left if selected => Some(SliderAction::Decrement),
+            Button::Left if hovering => Some(SliderAction::SwitchHover),
+            Button::Right if entering => Some(SliderAction::CursorRight),
+            Button::Right if selected => Some(SliderAction::Increment),
+            Button::Right if hovering => Some(SliderAction::SwitchHover),
+            // The mode only changes between moves, not while a handle is held
+            Button::L if !entering && !selected => Some(SliderAction::CycleMode),
+            Button::ZL if !entering && one_handle && self.has_presets() => {
+                Some(SliderAction::PreviousPreset)
+            }
+            Button::ZR if !entering && one_handle && self.has_presets() => {
+                Some(SliderAction::NextPreset)
+            }
+            _ => None,
+        }
+    }
+
+    /// Does what a button does to the slider in its current state
+    pub fn on_button(&mut self, button: Button) {
+        let action = match self.action(button) {
+            Some(action) => action,
+            None => return,
+        };
+        match action {
+            SliderAction::SelectHandle => self.select_deselect(),
+            SliderAction::DeselectHandle => self.deselect(),
+            SliderAction::BeginEntry => self.begin_entry(),
+            SliderAction::ConfirmEntry => {
+                // An invalid value leaves the entry open with its error shown
+                let _ = self.commit_entry();
+            }
+            SliderAction::CancelEntry => self.cancel_entry(),
+            SliderAction::DialUp => self.entry_mut().unwrap().dial_up(),
+            SliderAction::DialDown => self.entry_mut().unwrap().dial_down(),
+            SliderAction::CursorLeft => self.entry_mut().unwrap().cursor_left(),
+            SliderAction::CursorRight => self.entry_mut().unwrap().cursor_right(),
+            SliderAction::Increment => self.increment_selected(),
+            SliderAction::Decrement => self.decrement_selected(),
+            SliderAction::IncrementFast => self.increment_selected_fast(),
+            SliderAction::DecrementFast => self.decrement_selected_fast(),
+            SliderAction::SwitchHover => self.switch_hover(),
+            SliderAction::CycleMode => self.cycle_mode(),
+            SliderAction::PreviousPreset => self.previous_preset(),
+            SliderAction::NextPreset => self.next_preset(),
+        }
+    }
+
+    pub fn entry(&self) -> Option<&NumericEntry> {
+        dispatch_slider!(self, slider => slider.entry.as_ref())
+    }
+
+    pub fn entry_mut(&mut self) -> Option<&mut NumericEntry> {
+        dispatch_slider!(self, slider => slider.entry.as_mut())
+    }
+
+    pub fn has_presets(&self) -> bool {
+        dispatch_slider!(self, slider => !slider.presets.is_empty())
+    }
+
+    /// Whether exactly one handle is selected, rather than none or the whole range
+    pub fn is_single_handle_selected(&self) -> bool {
+        dispatch_slider!(self, slider => slider.selected_value().is_some())
+    }
+
+    pub fn is_handle_selected(&self) -> bool {
+        dispatch_slider!(self, slider => slider.is_handle_selected())
+    }
+
+    pub fn is_entering(&self) -> bool {
+        dispatch_slider!(self, slider => slider.is_entering())
+    }
+
+    pub fn begin_entry(&mut self) {
+        dispatch_slider!(self, slider => slider.begin_entry())
+    }
+
+    pub fn commit_entry(&mut self) -> Result<(), SliderEntryError> {
+        dispatch_slider!(self, slider => slider.commit_entry())
+    }
+
+    pub fn cancel_entry(&mut self) {
+        dispatch_slider!(self, slider => slider.cancel_entry())
+    }
+
+    pub fn select_deselect(&mut self) {
+        dispatch_slider!(self, slider => slider.select_deselect())
+    }
+
+    pub fn deselect(&mut self) {
+        dispatch_slider!(self, slider => slider.deselect())
+    }
+
+    pub fn switch_hover(&mut self) {
+        dispatch_slider!(self, slider => slider.switch_hover())
+    }
+
+    pub fn increment_selected(&mut self) {
+        dispatch_slider!(self, slider => slider.increment_selected())
+    }
+
+    pub fn decrement_selected(&mut self) {
+        dispatch_slider!(self, slider => slider.decrement_selected())
+    }
+
+    pub fn increment_selected_slow(&mut self) {
+        dispatch_slider!(self, slider => slider.increment_selected_slow())
+    }
+
+    pub fn increment_selected_fast(&mut self) {
+        dispatch_slider!(self, slider => slider.increment_selected_fast())
+    }
+
+    pub fn decrement_selected_slow(&mut self) {
+        dispatch_slider!(self, slider => slider.decrement_selected_slow())
+    }
+
+    pub fn decrement_selected_fast(&mut self) {
+        dispatch_slider!(self, slider => slider.decrement_selected_fast())
+    }
+
+    pub fn next_preset(&mut self) {
+        dispatch_slider!(self, slider => slider.next_preset())
+    }
+
+    pub fn previous_preset(&mut self) {
+        dispatch_slider!(self, slider => slider.previous_preset())
+    }
+
+    /// Selects the handle closest to a position along the slider, from 0.0 to 1.0
+    pub fn grab_nearest_at(&mut self, fraction: f64) {
+        dispatch_slider!(self, slider => slider.grab_nearest(slider.value_at(fraction)))
+    }
+
+    /// Moves the selected handle towards a position along the slider, from 0.0 to 1.0
+    pub fn drag_selected_to_fraction(&mut self, fraction: f64) {
+        dispatch_slider!(self, slider => slider.drag_selected_to(slider.value_at(fraction)))
+    }
+
+    /// The handle values, formatted for display. A single value if the handles are together.
+    pub fn value_summary(&self) -> String {
+        dispatch_slider!(self, slider => {
+            if slider.lower == slider.upper {
+                slider.format_value(slider.lower)
+            } else {
+                format!(
+                    "{}–{}",
+                    slider.format_value(slider.lower),
+                    slider.format_value(slider.upper)
+                )
+            }
+        })
+    }
+
+    /// Sets both handles from `[lower, upper]`, parsed as the slider's own value type
+    pub fn update_from_value(&mut self, value: &Value) {
+        dispatch_slider!(self, slider => {
+            let (lower, upper) = parse_range(value).expect(
+                "Exactly two values of the slider's type need to be passed to submenu.set() for slider!",
+            );
+            slider.lower = lower;
+            slider.upper = upper;
+        })
+    }
+
+    /// Whether `[lower, upper]` parses as the slider's own value type, in order and within range
+    pub fn check_value(&self, value: &Value) -> bool {
+        dispatch_slider!(self, slider => match parse_range(value) {
+            Ok((lower, upper)) => slider.min <= lower && lower <= upper && upper <= slider.max,
+            Err(_) => false,
+        })
+    }
+
+    /// Sets both handles from `[lower, upper]` given as bytes
+    pub fn update_from_bytes(&mut self, lower: u8, upper: u8) {
+        dispatch_slider!(self, slider => {
+            slider.lower = SliderValue::from_f64(lower as f64);
+            slider.upper = SliderValue::from_f64(upper as f64);
+        })
+    }
+}
+
+fn parse_range<T: SliderValue>(value: &Value) -> serde_json::Result<(T, T)> {
+    let values: [T; 2] = serde_json::from_value(value.clone())?;
+    Ok((values[0], values[1]))
+}
+
+impl Serialize for AnySlider {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        dispatch_slider!(self, slider => slider.serialize(serializer))
+    }
+}
@@ -0,0 +1,81 @@
+use core::time::Duration;
+
+use crate::round_f64;
+
+const DEFAULT_DELAY_MS: f64 = 400.0;
+const DEFAULT_INTERVAL_MS: f64 = 100.0;
+const DEFAULT_MIN_INTERVAL_MS: f64 = 33.0;
+const DEFAULT_FAST_AFTER_MS: f64 = 1500.0;
+
+/// Repeats a held button, first after a delay and then at a rate which speeds up the longer
+/// it is held. Timings are counted in ticks, which are whatever the host steps the menu by,
+/// e.g. one per frame in game.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AutoRepeat {
+    /// Ticks a button must be held before it first repeats. Zero disables repeating.
+    pub initial_delay: u32,
+    /// Ticks between the first repeats
+    pub interval: u32,
+    /// The shortest interval that acceleration reaches
+    pub min_interval: u32,
+    /// Number of repeats after which the interval shortens by a tick. Zero disables acceleration.
+    pub accelerate_every: u32,
+    /// Ticks a button must be held before slider handles move by their fast increment.
+    /// None keeps them on the slow increment. This is the held counterpart of a slider's
+    /// StepAcceleration, which only speeds up separate taps.
+    pub fast_after: Option<u32>,
+}
+
+impl AutoRepeat {
+    /// The default timings, converted to ticks `tick_length` apart
+    pub fn for_tick_length(tick_length: Duration) -> AutoRepeat {
+        let tick_ms = tick_length.as_secs_f64() * 1000.0;
+        let ticks = |ms: f64| (round_f64(ms / tick_ms) as u32).max(1);
+        AutoRepeat {
+            initial_delay: ticks(DEFAULT_DELAY_MS),
+            interval: ticks(DEFAULT_INTERVAL_MS),
+            min_interval: ticks(DEFAULT_MIN_INTERVAL_MS),
+            accelerate_every: 4,
+            fast_after: Some(ticks(DEFAULT_FAST_AFTER_MS)),
+        }
+    }
+
+    /// Whether a button which has been held for `held_ticks` repeats on that tick
+    pub fn repeats_at(&self, held_ticks: u32) -> bool {
+        if self.initial_delay == 0 {
+            return false;
+        }
+        let mut next = self.initial_delay;
+        let mut repeats = 0;
+        while next < held_ticks {
+            next = next.saturating_add(self.interval_after(repeats));
+            repeats += 1;
+        }
+        next == held_ticks
+    }
+
+    /// Whether a button which has been held for `held_ticks` moves slider handles quickly
+    pub fn is_fast(&self, held_ticks: u32) -> bool {
+        self.fast_after
+            .is_some_and(|fast_after| held_ticks >= fast_after)
+    }
+
+    /// Ticks from one repeat to the next, once there have been `repeats` repeats
+    fn interval_after(&self, repeats: u32) -> u32 {
+        let shortened = match self.accelerate_every {
+            0 => 0,
+            every => repeats / every,
+        };
+        self.interval
+            .saturating_sub(shortened)
+            .max(self.min_interval)
+            .max(1)
+    }
+}
+
+/// Timings for a host which ticks every frame at 60 frames per second
+impl Default for AutoRepeat {
+    fn default() -> AutoRepeat {
+        AutoRepeat::for_tick_length(Duration::from_secs(1) / 60)
+    }
+}
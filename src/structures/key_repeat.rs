@@ -0,0 +1,80 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+
+use crate::{Button, ButtonEvent};
+
+/// Turns key presses from a terminal, which doesn't report releases and repeats held keys
+/// by itself, into the presses, holds and releases the App expects.
+///
+/// A press only counts as the terminal repeating a held key once it comes within
+/// `repeat_timeout` of the previous one. Slower presses are separate taps, so each of them
+/// acts and can build up a slider's StepAcceleration.
+/// Times are whatever the host measures from, e.g. the elapsed time since it started.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRepeatFilter {
+    /// How long a key counts as held after a press which wasn't a repeat. Long enough for
+    /// the terminal's key repeat to start.
+    pub first_repeat_timeout: Duration,
+    /// How long a key counts as held after a repeat. A little longer than the terminal's
+    /// repeat interval.
+    pub repeat_timeout: Duration,
+    /// Keys which are down, with when their last press came and whether it was a repeat
+    held: BTreeMap<Button, (Duration, bool)>,
+}
+
+impl KeyRepeatFilter {
+    pub fn new(first_repeat_timeout: Duration, repeat_timeout: Duration) -> KeyRepeatFilter {
+        KeyRepeatFilter {
+            first_repeat_timeout,
+            repeat_timeout,
+            held: BTreeMap::new(),
+        }
+    }
+
+    /// The events to send for a key press at `now`. Nothing for the terminal repeating a held
+    /// key, and a release before the press if the key was still counted as held.
+    pub fn press(&mut self, button: Button, now: Duration) -> &'static [ButtonEvent] {
+        match self.held.get_mut(&button) {
+            Some((last_press, repeating))
+                if now.saturating_sub(*last_press) < self.repeat_timeout =>
+            {
+                *last_press = now;
+                *repeating = true;
+                &[]
+            }
+            Some(held) => {
+                *held = (now, false);
+                &[ButtonEvent::Release, ButtonEvent::Press]
+            }
+            None => {
+                self.held.insert(button, (now, false));
+                &[ButtonEvent::Press]
+            }
+        }
+    }
+
+    /// Stops counting keys as held once no press has come for them in time.
+    /// Returns the keys to send a release for.
+    pub fn release_expired(&mut self, now: Duration) -> Vec<Button> {
+        let mut released = Vec::new();
+        let (first_repeat_timeout, repeat_timeout) =
+            (self.first_repeat_timeout, self.repeat_timeout);
+        self.held.retain(|button, (last_press, repeating)| {
+            let timeout = if *repeating {
+                repeat_timeout
+            } else {
+                first_repeat_timeout
+            };
+            let held = now.saturating_sub(*last_press) < timeout;
+            if !held {
+                released.push(*button);
+            }
+            held
+        });
+        released
+    }
+
+    pub fn is_held(&self, button: Button) -> bool {
+        self.held.contains_key(&button)
+    }
+}
@@ -1,6 +1,18 @@
+mod any_slider;
+mod auto_repeat;
+mod key_repeat;
+mod numeric_entry;
+mod selection;
+mod slider_value;
 mod stateful_list;
 mod stateful_slider;
 mod stateful_table;
+pub use any_slider::*;
+pub use auto_repeat::*;
+pub use key_repeat::*;
+pub use numeric_entry::*;
+pub use selection::*;
+pub use slider_value::*;
 pub use stateful_list::*;
 pub use stateful_slider::*;
 pub use stateful_table::*;
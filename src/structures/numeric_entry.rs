@@ -0,0 +1,160 @@
+use alloc::format;
+use core::fmt;
+
+use crate::{abs_f64, pow10, round_f64, trunc_f64};
+
+const MAX_ENTRY_DIGITS: usize = 12;
+
+/// Keys which can be typed into a NumericEntry
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EntryKey {
+    Digit(u8),
+    Minus,
+    Backspace,
+}
+
+/// Why an entered value was rejected
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SliderEntryError {
+    BelowMinimum,
+    AboveMaximum,
+    /// The value would move the selected handle past the other handle
+    CrossesOtherHandle,
+    NoHandleSelected,
+}
+
+impl fmt::Display for SliderEntryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            SliderEntryError::BelowMinimum => "Value is below the minimum",
+            SliderEntryError::AboveMaximum => "Value is above the maximum",
+            SliderEntryError::CrossesOtherHandle => "Value would cross the other handle",
+            SliderEntryError::NoHandleSelected => "No handle selected",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// A fixed-width number being entered for a slider handle.
+///
+/// Digits can be typed calculator-style, shifting in from the right,
+/// or dialed in one at a time by moving a cursor and changing the digit under it.
+/// If the number can be negative, the sign occupies the first cursor slot.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NumericEntry {
+    digits: [u8; MAX_ENTRY_DIGITS],
+    /// Number of digits, including those after the decimal point
+    pub len: usize,
+    /// Number of digits after the decimal point
+    pub decimals: usize,
+    pub negative: bool,
+    pub has_sign: bool,
+    /// Cursor slot, where slot 0 is the sign if `has_sign` is set
+    pub cursor: usize,
+    pub error: Option<SliderEntryError>,
+}
+
+impl NumericEntry {
+    /// Creates an entry holding `value`, wide enough for any value up to `max_abs`.
+    /// Decimal places beyond the most digits an entry holds are dropped.
+    pub fn new(value: f64, max_abs: f64, decimals: usize, has_sign: bool) -> NumericEntry {
+        let integer_digits = format!("{:.0}", trunc_f64(abs_f64(max_abs))).len();
+        let len = (integer_digits + decimals).min(MAX_ENTRY_DIGITS);
+        let decimals = decimals.min(len);
+        let mut entry = NumericEntry {
+            digits: [0; MAX_ENTRY_DIGITS],
+            len,
+            decimals,
+            negative: value < 0.0,
+            has_sign,
+            cursor: 0,
+            error: None,
+        };
+        let mut scaled = round_f64(abs_f64(value) * pow10(decimals)) as u64;
+        for idx in (0..len).rev() {
+            entry.digits[idx] = (scaled % 10) as u8;
+            scaled /= 10;
+        }
+        entry
+    }
+
+    /// The digits, most significant first
+    pub fn digits(&self) -> &[u8] {
+        &self.digits[..self.len]
+    }
+
+    pub fn value(&self) -> f64 {
+        let scaled = self
+            .digits()
+            .iter()
+            .fold(0u64, |acc, digit| acc * 10 + *digit as u64);
+        let value = scaled as f64 / pow10(self.decimals);
+        if self.negative {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn slots(&self) -> usize {
+        self.len + if self.has_sign { 1 } else { 0 }
+    }
+
+    /// The digit index under the cursor, or None if the cursor is on the sign
+    fn cursor_digit(&self) -> Option<usize> {
+        match (self.has_sign, self.cursor) {
+            (true, 0) => None,
+            (true, cursor) => Some(cursor - 1),
+            (false, cursor) => Some(cursor),
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        let slots = self.slots();
+        self.cursor = (self.cursor + slots - 1) % slots;
+    }
+
+    pub fn cursor_right(&mut self) {
+        self.cursor = (self.cursor + 1) % self.slots();
+    }
+
+    /// Increases the digit under the cursor, wrapping from 9 to 0, or flips the sign
+    pub fn dial_up(&mut self) {
+        self.error = None;
+        match self.cursor_digit() {
+            Some(idx) => self.digits[idx] = (self.digits[idx] + 1) % 10,
+            None => self.negative = !self.negative,
+        }
+    }
+
+    /// Decreases the digit under the cursor, wrapping from 0 to 9, or flips the sign
+    pub fn dial_down(&mut self) {
+        self.error = None;
+        match self.cursor_digit() {
+            Some(idx) => self.digits[idx] = (self.digits[idx] + 9) % 10,
+            None => self.negative = !self.negative,
+        }
+    }
+
+    pub fn type_key(&mut self, key: EntryKey) {
+        self.error = None;
+        let len = self.len;
+        match key {
+            EntryKey::Digit(digit) => {
+                // Shift everything left and add the new digit at the end
+                self.digits.copy_within(1..len, 0);
+                self.digits[len - 1] = digit % 10;
+            }
+            EntryKey::Backspace => {
+                // Shift everything right, dropping the last digit
+                self.digits.copy_within(0..len - 1, 1);
+                self.digits[0] = 0;
+            }
+            EntryKey::Minus => {
+                if self.has_sign {
+                    self.negative = !self.negative;
+                }
+            }
+        }
+    }
+}
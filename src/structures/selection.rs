@@ -0,0 +1,127 @@
+/// What is selected in a table: a whole row, or a single cell of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableSelection {
+    Row(usize),
+    Cell { row: usize, col: usize },
+}
+
+impl Default for TableSelection {
+    fn default() -> Self {
+        TableSelection::Cell { row: 0, col: 0 }
+    }
+}
+
+/// Selection and scroll position of a table. This is the menu's own state rather than a
+/// terminal widget's, and is converted for drawing when the terminal UI is built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TableState {
+    offset: usize,
+    selected: Option<TableSelection>,
+}
+
+impl TableState {
+    pub fn with_selected(mut self, selected: Option<TableSelection>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The first row on display
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn offset_mut(&mut self) -> &mut usize {
+        &mut self.offset
+    }
+
+    pub fn selected(&self) -> Option<TableSelection> {
+        self.selected
+    }
+
+    /// Selects a row or cell. Clearing the selection also scrolls back to the top.
+    pub fn select(&mut self, selected: Option<TableSelection>) {
+        self.selected = selected;
+        if selected.is_none() {
+            self.offset = 0;
+        }
+    }
+
+    pub fn selected_row(&self) -> Option<usize> {
+        match self.selected {
+            Some(TableSelection::Row(row)) | Some(TableSelection::Cell { row, .. }) => Some(row),
+            None => None,
+        }
+    }
+
+    pub fn selected_col(&self) -> Option<usize> {
+        match self.selected {
+            Some(TableSelection::Cell { col, .. }) => Some(col),
+            _ => None,
+        }
+    }
+
+    /// Moves to another row, keeping the selected column if there is one
+    pub fn select_row(&mut self, row: Option<usize>) {
+        let selected = row.map(|row| match self.selected_col() {
+            Some(col) => TableSelection::Cell { row, col },
+            None => TableSelection::Row(row),
+        });
+        self.select(selected);
+    }
+
+    /// Moves to another column of the selected row, or of the first row if none is selected.
+    /// Clearing the column leaves the whole row selected.
+    pub fn select_col(&mut self, col: Option<usize>) {
+        let row = self.selected_row();
+        self.selected = match col {
+            Some(col) => Some(TableSelection::Cell {
+                row: row.unwrap_or(0),
+                col,
+            }),
+            None => row.map(TableSelection::Row),
+        };
+    }
+}
+
+/// Selection and scroll position of a list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ListState {
+    offset: usize,
+    selected: Option<usize>,
+}
+
+impl ListState {
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn offset_mut(&mut self) -> &mut usize {
+        &mut self.offset
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn select(&mut self, selected: Option<usize>) {
+        self.selected = selected;
+        if selected.is_none() {
+            self.offset = 0;
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use core::fmt::{Debug, Display};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Numeric types which can be used as the values of a StatefulSlider
+pub trait SliderValue:
+    Copy + PartialOrd + Debug + Display + Serialize + DeserializeOwned + 'static
+{
+    /// Number of decimal places shown in labels by default
+    const DEFAULT_PRECISION: usize;
+    /// Whether the type can only hold whole numbers
+    const IS_INTEGER: bool;
+
+    /// Adds `amount`, saturating at the numeric bounds of the type
+    fn step_up(self, amount: Self) -> Self;
+    /// Subtracts `amount`, saturating at the numeric bounds of the type
+    fn step_down(self, amount: Self) -> Self;
+    fn to_f64(self) -> f64;
+    /// Converts from f64, rounding to the nearest value for integer types
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_slider_value_int {
+    ($($t:ty),*) => {
+        $(
+            impl SliderValue for $t {
+                const DEFAULT_PRECISION: usize = 0;
+                const IS_INTEGER: bool = true;
+
+                fn step_up(self, amount: Self) -> Self {
+                    self.saturating_add(amount)
+                }
+                fn step_down(self, amount: Self) -> Self {
+                    self.saturating_sub(amount)
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    round_f64(value) as $t
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_slider_value_float {
+    ($($t:ty),*) => {
+        $(
+            impl SliderValue for $t {
+                const DEFAULT_PRECISION: usize = 2;
+                const IS_INTEGER: bool = false;
+
+                fn step_up(self, amount: Self) -> Self {
+                    self + amount
+                }
+                fn step_down(self, amount: Self) -> Self {
+                    self - amount
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(value: f64) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_slider_value_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+impl_slider_value_float!(f32, f64);
+
+/// The lesser of two values, for types which are only PartialOrd
+pub(crate) fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// The greater of two values, for types which are only PartialOrd
+pub(crate) fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+// The float methods below live in std, as they call into the platform's maths library, so the
+// slider has its own versions of the few it needs
+
+/// The absolute value of a float
+pub(crate) fn abs_f64(value: f64) -> f64 {
+    f64::from_bits(value.to_bits() & !(1 << 63))
+}
+
+/// Rounds towards zero
+pub(crate) fn trunc_f64(value: f64) -> f64 {
+    // Floats this large have no fractional part, and may not fit in an i64
+    if abs_f64(value) >= (1u64 << 52) as f64 || value.is_nan() {
+        value
+    } else {
+        value as i64 as f64
+    }
+}
+
+/// Rounds to the nearest whole number, with halves rounded away from zero
+pub(crate) fn round_f64(value: f64) -> f64 {
+    let truncated = trunc_f64(value);
+    if abs_f64(value - truncated) >= 0.5 {
+        truncated + if value < 0.0 { -1.0 } else { 1.0 }
+    } else {
+        truncated
+    }
+}
+
+/// Ten to the power of `exponent`
+pub(crate) fn pow10(exponent: usize) -> f64 {
+    (0..exponent).fold(1.0, |acc, _| acc * 10.0)
+}
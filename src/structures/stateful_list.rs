@@ -1,6 +1,8 @@
-use ratatui::widgets::ListState;
+use alloc::vec::Vec;
 use serde::ser::{Serialize, SerializeSeq, Serializer};
 
+use crate::ListState;
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct StatefulList<T: Serialize> {
     pub state: ListState,
@@ -9,12 +11,18 @@ pub struct StatefulList<T: Serialize> {
 
 impl<T: Serialize> IntoIterator for StatefulList<T> {
     type Item = T;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = alloc::vec::IntoIter<Self::Item>;
     fn into_iter(self) -> Self::IntoIter {
         self.items.into_iter()
     }
 }
 
+impl<T: Serialize> Default for StatefulList<T> {
+    fn default() -> StatefulList<T> {
+        StatefulList::new()
+    }
+}
+
 impl<T: Serialize> StatefulList<T> {
     pub fn new() -> StatefulList<T> {
         StatefulList {
@@ -70,6 +78,12 @@ impl<T: Serialize> StatefulList<T> {
         }
     }
 
+    pub fn selected_item(&self) -> Option<&T> {
+        self.state
+            .selected()
+            .map(|selected_index| &self.items[selected_index])
+    }
+
     pub fn get_before_selected(&mut self) -> Option<&mut T> {
         let len = self.items.len();
         if let Some(selected_index) = self.state.selected() {
@@ -114,7 +128,7 @@ impl<T: Serialize> StatefulList<T> {
     pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
         self.items.iter()
     }
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<T> {
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
         self.items.iter_mut()
     }
 }
@@ -1,108 +1,521 @@
-use serde::{Serialize, Serializer};
+use alloc::{format, string::String, vec, vec::Vec};
+use core::time::Duration;
+use serde::{Deserialize, Serialize, Serializer};
+#[cfg(feature = "std")]
+use std::{sync::OnceLock, time::Instant};
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+use crate::{
+    abs_f64, partial_max, partial_min, round_f64, NumericEntry, SliderEntryError, SliderValue,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
 pub enum SliderState {
     LowerHover,
     UpperHover,
     LowerSelected,
     UpperSelected,
+    /// Both handles, for moving the range as one
+    RangeHover,
+    RangeSelected,
     None,
 }
 
+/// How a selected handle interacts with the other handle
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SliderMode {
+    /// A handle stops when it reaches the other handle
+    #[default]
+    Clamp,
+    /// A handle pushes the other handle along when they meet
+    Push,
+    /// Moving either handle slides the whole range, keeping its width
+    LockedWidth,
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub struct StatefulSlider {
+enum StepDirection {
+    Increase,
+    Decrease,
+}
+
+/// Returns the time elapsed since some fixed point, for timing presses
+pub type Clock = fn() -> Duration;
+
+/// The default clock, measured from the first time it's read
+#[cfg(feature = "std")]
+fn system_clock() -> Duration {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed()
+}
+
+/// Escalates repeated presses in the same direction from the slow increment to the fast one.
+/// Presses more than `window` apart, or on a different handle, start a new streak.
+/// Only separate taps count: a held button's repeats are sped up by `AutoRepeat::fast_after`
+/// instead, and don't add to the streak.
+///
+/// Presses are timed with `clock`, which is the system clock by default. Without std there
+/// is no default clock, so presses only accelerate once the host sets one.
+#[derive(Clone, Copy, Debug)]
+pub struct StepAcceleration {
+    pub window: Duration,
+    /// Number of slow steps in a streak before switching to fast steps. Zero disables acceleration.
+    pub presses_until_fast: u32,
+    pub clock: Option<Clock>,
+    streak: u32,
+    last_press: Option<(SliderState, StepDirection, Duration)>,
+}
+
+impl StepAcceleration {
+    pub fn new(window: Duration, presses_until_fast: u32) -> StepAcceleration {
+        StepAcceleration {
+            window,
+            presses_until_fast,
+            #[cfg(feature = "std")]
+            clock: Some(system_clock),
+            #[cfg(not(feature = "std"))]
+            clock: None,
+            streak: 0,
+            last_press: None,
+        }
+    }
+
+    pub fn with_clock(mut self, clock: Clock) -> StepAcceleration {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Records a press and returns whether it should use the fast increment
+    fn record_press(&mut self, state: SliderState, direction: StepDirection) -> bool {
+        let Some(clock) = self.clock else {
+            return false;
+        };
+        let now = clock();
+        let continues_streak = match self.last_press {
+            Some((last_state, last_direction, last_time)) => {
+                last_state == state
+                    && last_direction == direction
+                    && now.saturating_sub(last_time) <= self.window
+            }
+            None => false,
+        };
+        self.streak = if continues_streak {
+            self.streak.saturating_add(1)
+        } else {
+            1
+        };
+        self.last_press = Some((state, direction, now));
+        self.presses_until_fast > 0 && self.streak > self.presses_until_fast
+    }
+}
+
+impl Default for StepAcceleration {
+    fn default() -> StepAcceleration {
+        StepAcceleration::new(Duration::from_millis(300), 5)
+    }
+}
+
+// Only the configuration is compared, not the clock or the current streak
+impl PartialEq for StepAcceleration {
+    fn eq(&self, other: &Self) -> bool {
+        self.window == other.window && self.presses_until_fast == other.presses_until_fast
+    }
+}
+
+/// The most decimal places a slider shows, whatever its precision is set to.
+/// Finer digits than this are mostly rounding error.
+const MAX_PRECISION: usize = 9;
+
+/// A named position on a slider, such as 100 "Kill %"
+#[derive(Clone, PartialEq, Debug)]
+pub struct SliderTick<T: SliderValue> {
+    pub value: T,
+    pub label: String,
+}
+
+/// How a slider's values are displayed
+#[derive(Clone, PartialEq, Debug)]
+pub struct SliderFormat<T: SliderValue> {
+    /// Appended to every displayed value, e.g. "%", "f" or "°"
+    pub unit: String,
+    /// If set, values are frames and are displayed as seconds at this frame rate
+    pub frames_per_second: Option<u32>,
+    /// Named positions drawn under the slider
+    pub ticks: Vec<SliderTick<T>>,
+    /// Move handles from tick to tick instead of by the increment amounts
+    pub snap_to_ticks: bool,
+}
+
+impl<T: SliderValue> Default for SliderFormat<T> {
+    fn default() -> SliderFormat<T> {
+        SliderFormat {
+            unit: String::new(),
+            frames_per_second: None,
+            ticks: vec![],
+            snap_to_ticks: false,
+        }
+    }
+}
+
+/// A double-ended slider over any numeric type.
+/// Maintains min <= lower <= upper <= max.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatefulSlider<T: SliderValue = u32> {
     pub state: SliderState,
-    pub lower: u32,
-    pub upper: u32,
-    pub min: u32,
-    pub max: u32,
-    pub incr_amount_slow: u32,
-    pub incr_amount_fast: u32,
+    pub lower: T,
+    pub upper: T,
+    pub min: T,
+    pub max: T,
+    pub incr_amount_slow: T,
+    pub incr_amount_fast: T,
+    pub accel: StepAcceleration,
+    /// Number of decimal places shown in labels. Ignored for integer types unless
+    /// they are converted from frames to seconds. Unless set, seconds are shown to
+    /// two decimal places and other values use the type's default.
+    pub precision: Option<usize>,
+    pub format: SliderFormat<T>,
+    pub mode: SliderMode,
+    /// Include the RangeHover state when switching between handles
+    pub range_handle: bool,
+    /// Values the selected handle can jump between, in ascending order
+    pub presets: Vec<T>,
+    /// A value being typed or dialed in for the selected handle
+    pub entry: Option<NumericEntry>,
+}
+
+impl StatefulSlider<u32> {
+    pub fn new() -> StatefulSlider<u32> {
+        StatefulSlider::with_range(0, 150, 1, 10)
+    }
+}
+
+impl Default for StatefulSlider<u32> {
+    fn default() -> StatefulSlider<u32> {
+        StatefulSlider::new()
+    }
 }
 
-impl StatefulSlider {
-    pub fn new() -> StatefulSlider {
+impl<T: SliderValue> StatefulSlider<T> {
+    /// Creates a slider with both handles at the extremes of `min..=max`
+    pub fn with_range(
+        min: T,
+        max: T,
+        incr_amount_slow: T,
+        incr_amount_fast: T,
+    ) -> StatefulSlider<T> {
+        assert!(min <= max, "Slider minimum must not exceed the maximum!");
         StatefulSlider {
             state: SliderState::LowerHover,
-            lower: 0,
-            upper: 150,
-            min: 0,
-            max: 150,
-            incr_amount_slow: 1,
-            incr_amount_fast: 10,
+            lower: min,
+            upper: max,
+            min,
+            max,
+            incr_amount_slow,
+            incr_amount_fast,
+            accel: StepAcceleration::default(),
+            precision: None,
+            format: SliderFormat::default(),
+            mode: SliderMode::default(),
+            range_handle: false,
+            presets: vec![],
+            entry: None,
+        }
+    }
+
+    /// Number of decimal places shown in labels, at most MAX_PRECISION
+    pub fn decimals(&self) -> usize {
+        match (self.precision, self.format.frames_per_second) {
+            (Some(precision), _) => precision.min(MAX_PRECISION),
+            // Whole seconds would hide most frame counts
+            (None, Some(_)) => 2,
+            (None, None) => T::DEFAULT_PRECISION,
+        }
+    }
+
+    /// Formats a value for display using the slider's precision and format
+    pub fn format_value(&self, value: T) -> String {
+        let decimals = self.decimals();
+        let number = match self.format.frames_per_second {
+            Some(fps) => format!("{:.*}", decimals, value.to_f64() / fps as f64),
+            None => format!("{:.*}", decimals, value),
+        };
+        number + &self.format.unit
+    }
+
+    /// The nearest tick strictly above or below `value`, if any
+    fn next_tick(&self, value: T, increasing: bool) -> Option<T> {
+        let mut nearest: Option<T> = None;
+        for tick in self.format.ticks.iter() {
+            let beyond = if increasing {
+                tick.value > value
+            } else {
+                tick.value < value
+            };
+            let closer = match nearest {
+                Some(n) => (tick.value < n) == increasing,
+                None => true,
+            };
+            if beyond && closer {
+                nearest = Some(tick.value);
+            }
+        }
+        nearest
+    }
+
+    /// The value a handle moves to when stepped by `amount`
+    fn step_target(&self, value: T, amount: T, increasing: bool) -> T {
+        let snapped = if self.format.snap_to_ticks {
+            self.next_tick(value, increasing)
+        } else {
+            None
+        };
+        match snapped {
+            Some(tick) => tick,
+            None if increasing => value.step_up(amount),
+            None => value.step_down(amount),
+        }
+    }
+
+    /// Increments the selected handle, switching to the fast increment
+    /// after enough presses in quick succession
+    pub fn increment_selected(&mut self) {
+        if self.accel.record_press(self.state, StepDirection::Increase) {
+            self.increment_selected_fast();
+        } else {
+            self.increment_selected_slow();
+        }
+    }
+
+    /// Decrements the selected handle, switching to the fast increment
+    /// after enough presses in quick succession
+    pub fn decrement_selected(&mut self) {
+        if self.accel.record_press(self.state, StepDirection::Decrease) {
+            self.decrement_selected_fast();
+        } else {
+            self.decrement_selected_slow();
         }
     }
 
     pub fn increment_selected_slow(&mut self) {
+        self.increment_selected_by(self.incr_amount_slow);
+    }
+
+    pub fn increment_selected_fast(&mut self) {
+        self.increment_selected_by(self.incr_amount_fast);
+    }
+
+    pub fn decrement_selected_slow(&mut self) {
+        self.decrement_selected_by(self.incr_amount_slow);
+    }
+
+    pub fn decrement_selected_fast(&mut self) {
+        self.decrement_selected_by(self.incr_amount_fast);
+    }
+
+    /// Whether the selected handle moves the whole range rather than a single handle
+    fn moves_range(&self) -> bool {
         match self.state {
-            SliderState::LowerSelected => {
-                self.lower = self
-                    .lower
-                    .saturating_add(self.incr_amount_slow)
-                    .min(self.upper); // Don't allow lower > upper
+            SliderState::RangeSelected => true,
+            SliderState::LowerSelected | SliderState::UpperSelected => {
+                self.mode == SliderMode::LockedWidth
             }
-            SliderState::UpperSelected => {
-                self.upper = self
-                    .upper
-                    .saturating_add(self.incr_amount_slow)
-                    .min(self.max); // Don't allow upper > max
+            _ => false,
+        }
+    }
+
+    /// Slides both handles by up to `amount`, stopping when either reaches its limit
+    fn shift_range(&mut self, amount: T, increasing: bool) {
+        if increasing {
+            let shift = partial_min(amount, self.max.step_down(self.upper));
+            self.lower = self.lower.step_up(shift);
+            self.upper = self.upper.step_up(shift);
+        } else {
+            let shift = partial_min(amount, self.lower.step_down(self.min));
+            self.lower = self.lower.step_down(shift);
+            self.upper = self.upper.step_down(shift);
+        }
+    }
+
+    fn increment_selected_by(&mut self, amount: T) {
+        if self.moves_range() {
+            self.shift_range(amount, true);
+            return;
+        }
+        match (self.state, self.mode) {
+            (SliderState::LowerSelected, SliderMode::Push) => {
+                // Don't allow lower > max, and push upper along with it
+                self.lower = partial_min(self.step_target(self.lower, amount, true), self.max);
+                self.upper = partial_max(self.upper, self.lower);
+            }
+            (SliderState::LowerSelected, _) => {
+                // Don't allow lower > upper
+                self.lower = partial_min(self.step_target(self.lower, amount, true), self.upper);
+            }
+            (SliderState::UpperSelected, _) => {
+                // Don't allow upper > max
+                self.upper = partial_min(self.step_target(self.upper, amount, true), self.max);
             }
             _ => {}
         }
     }
 
-    pub fn increment_selected_fast(&mut self) {
-        match self.state {
-            SliderState::LowerSelected => {
-                self.lower = self
-                    .lower
-                    .saturating_add(self.incr_amount_fast)
-                    .min(self.upper); // Don't allow lower > upper
+    fn decrement_selected_by(&mut self, amount: T) {
+        if self.moves_range() {
+            self.shift_range(amount, false);
+            return;
+        }
+        match (self.state, self.mode) {
+            (SliderState::LowerSelected, _) => {
+                // Don't allow lower < min
+                self.lower = partial_max(self.step_target(self.lower, amount, false), self.min);
             }
-            SliderState::UpperSelected => {
-                self.upper = self
-                    .upper
-                    .saturating_add(self.incr_amount_fast)
-                    .min(self.max); // Don't allow upper > max
+            (SliderState::UpperSelected, SliderMode::Push) => {
+                // Don't allow upper < min, and push lower along with it
+                self.upper = partial_max(self.step_target(self.upper, amount, false), self.min);
+                self.lower = partial_min(self.lower, self.upper);
+            }
+            (SliderState::UpperSelected, _) => {
+                // Don't allow upper < lower
+                self.upper = partial_max(self.step_target(self.upper, amount, false), self.lower);
             }
             _ => {}
         }
     }
 
-    pub fn decrement_selected_slow(&mut self) {
+    /// The value of the selected handle, if exactly one handle is selected
+    pub fn selected_value(&self) -> Option<T> {
+        match self.state {
+            SliderState::LowerSelected => Some(self.lower),
+            SliderState::UpperSelected => Some(self.upper),
+            _ => None,
+        }
+    }
+
+    /// Moves the selected handle to `value`, if it is within the limits and doesn't
+    /// cross the other handle. In push mode, the other handle is pushed along instead.
+    /// In locked width mode the whole range moves, stopping when the other handle
+    /// reaches its limit.
+    pub fn set_selected(&mut self, value: T) -> Result<(), SliderEntryError> {
+        if value < self.min {
+            return Err(SliderEntryError::BelowMinimum);
+        }
+        if value > self.max {
+            return Err(SliderEntryError::AboveMaximum);
+        }
+        if self.mode == SliderMode::LockedWidth {
+            let current = self
+                .selected_value()
+                .ok_or(SliderEntryError::NoHandleSelected)?;
+            if value > current {
+                self.shift_range(value.step_down(current), true);
+            } else {
+                self.shift_range(current.step_down(value), false);
+            }
+            return Ok(());
+        }
+        let push = self.mode == SliderMode::Push;
         match self.state {
             SliderState::LowerSelected => {
-                self.lower = self
-                    .lower
-                    .saturating_sub(self.incr_amount_slow)
-                    .max(self.min); // Don't allow lower < min
+                if value > self.upper && !push {
+                    return Err(SliderEntryError::CrossesOtherHandle);
+                }
+                self.lower = value;
+                self.upper = partial_max(self.upper, value);
             }
             SliderState::UpperSelected => {
-                self.upper = self
-                    .upper
-                    .saturating_sub(self.incr_amount_slow)
-                    .max(self.lower); // Don't allow upper < lower
+                if value < self.lower && !push {
+                    return Err(SliderEntryError::CrossesOtherHandle);
+                }
+                self.upper = value;
+                self.lower = partial_min(self.lower, value);
             }
-            _ => {}
+            _ => return Err(SliderEntryError::NoHandleSelected),
         }
+        Ok(())
     }
 
-    pub fn decrement_selected_fast(&mut self) {
-        match self.state {
-            SliderState::LowerSelected => {
-                self.lower = self
-                    .lower
-                    .saturating_sub(self.incr_amount_fast)
-                    .max(self.min); // Don't allow lower < min
+    /// Moves the selected handle to the next preset above its value, wrapping around
+    pub fn next_preset(&mut self) {
+        self.cycle_preset(true);
+    }
+
+    /// Moves the selected handle to the next preset below its value, wrapping around
+    pub fn previous_preset(&mut self) {
+        self.cycle_preset(false);
+    }
+
+    fn cycle_preset(&mut self, forward: bool) {
+        let current = match self.selected_value() {
+            Some(value) => value,
+            None => return,
+        };
+        let mut candidates: Vec<T> = Vec::with_capacity(self.presets.len());
+        if forward {
+            candidates.extend(self.presets.iter().copied().filter(|p| *p > current));
+            candidates.extend(self.presets.iter().copied().filter(|p| *p <= current));
+        } else {
+            candidates.extend(self.presets.iter().rev().copied().filter(|p| *p < current));
+            candidates.extend(self.presets.iter().rev().copied().filter(|p| *p >= current));
+        }
+        // Skip any presets the handle can't reach, or can't move towards with the range locked
+        for candidate in candidates {
+            if candidate != current
+                && self.set_selected(candidate).is_ok()
+                && self.selected_value() != Some(current)
+            {
+                return;
             }
-            SliderState::UpperSelected => {
-                self.upper = self
-                    .upper
-                    .saturating_sub(self.incr_amount_fast)
-                    .max(self.lower); // Don't allow upper < lower
+        }
+    }
+
+    /// Starts entering a value for the selected handle, beginning from its current value
+    pub fn begin_entry(&mut self) {
+        let value = match self.selected_value() {
+            Some(value) => value,
+            None => return,
+        };
+        let decimals = if T::IS_INTEGER { 0 } else { self.decimals() };
+        let max_abs = partial_max(abs_f64(self.min.to_f64()), abs_f64(self.max.to_f64()));
+        let has_sign = self.min.to_f64() < 0.0;
+        self.entry = Some(NumericEntry::new(
+            value.to_f64(),
+            max_abs,
+            decimals,
+            has_sign,
+        ));
+    }
+
+    /// Applies the entered value to the selected handle and ends the entry.
+    /// If the value is invalid, the entry stays open with the error set.
+    pub fn commit_entry(&mut self) -> Result<(), SliderEntryError> {
+        let value = match self.entry {
+            Some(entry) => entry.value(),
+            None => return Ok(()),
+        };
+        // Check the limits before converting, since the conversion saturates
+        let result = if value < self.min.to_f64() {
+            Err(SliderEntryError::BelowMinimum)
+        } else if value > self.max.to_f64() {
+            Err(SliderEntryError::AboveMaximum)
+        } else {
+            self.set_selected(T::from_f64(value))
+        };
+        match result {
+            Ok(()) => self.entry = None,
+            Err(error) => {
+                if let Some(entry) = self.entry.as_mut() {
+                    entry.error = Some(error);
+                }
             }
-            _ => {}
         }
+        result
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    pub fn is_entering(&self) -> bool {
+        self.entry.is_some()
     }
 
     pub fn select_deselect(&mut self) {
@@ -111,6 +524,8 @@ impl StatefulSlider {
             SliderState::LowerSelected => SliderState::LowerHover,
             SliderState::UpperHover => SliderState::UpperSelected,
             SliderState::UpperSelected => SliderState::UpperHover,
+            SliderState::RangeHover => SliderState::RangeSelected,
+            SliderState::RangeSelected => SliderState::RangeHover,
             SliderState::None => SliderState::None,
         }
     }
@@ -119,24 +534,96 @@ impl StatefulSlider {
         self.state = match self.state {
             SliderState::LowerSelected => SliderState::LowerHover,
             SliderState::UpperSelected => SliderState::UpperHover,
+            SliderState::RangeSelected => SliderState::RangeHover,
             _ => self.state,
         }
     }
 
+    /// Switches to the next mode: clamp, push, then locked width
+    pub fn cycle_mode(&mut self) {
+        self.mode = match self.mode {
+            SliderMode::Clamp => SliderMode::Push,
+            SliderMode::Push => SliderMode::LockedWidth,
+            SliderMode::LockedWidth => SliderMode::Clamp,
+        };
+    }
+
+    /// Cycles the hovered handle: lower, upper, then both handles if `range_handle` is set
     pub fn switch_hover(&mut self) {
         self.state = match self.state {
             SliderState::LowerHover => SliderState::UpperHover,
+            SliderState::UpperHover if self.range_handle => SliderState::RangeHover,
             SliderState::UpperHover => SliderState::LowerHover,
+            SliderState::RangeHover => SliderState::LowerHover,
             _ => self.state,
         }
     }
 
-    pub fn is_handle_selected(&mut self) -> bool {
-        self.state == SliderState::LowerSelected || self.state == SliderState::UpperSelected
+    pub fn is_handle_selected(&self) -> bool {
+        matches!(
+            self.state,
+            SliderState::LowerSelected | SliderState::UpperSelected | SliderState::RangeSelected
+        )
+    }
+
+    /// Position of a value along the slider, from 0.0 at the minimum to 1.0 at the maximum
+    pub fn fraction(&self, value: T) -> f64 {
+        let range = self.max.to_f64() - self.min.to_f64();
+        if range > 0.0 {
+            ((value.to_f64() - self.min.to_f64()) / range).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The value at a position along the slider, from 0.0 at the minimum to 1.0 at the maximum,
+    /// rounded to a whole number of slow increments
+    pub fn value_at(&self, fraction: f64) -> T {
+        let (min, max) = (self.min.to_f64(), self.max.to_f64());
+        let step = self.incr_amount_slow.to_f64();
+        let mut offset = fraction.clamp(0.0, 1.0) * (max - min);
+        if step > 0.0 {
+            offset = round_f64(offset / step) * step;
+        }
+        T::from_f64((min + offset).min(max))
+    }
+
+    /// Selects whichever handle is closest to `value`, e.g. where the slider was clicked
+    pub fn grab_nearest(&mut self, value: T) {
+        let to_lower = abs_f64(value.to_f64() - self.lower.to_f64());
+        let to_upper = abs_f64(value.to_f64() - self.upper.to_f64());
+        // When the handles are together, grab the one on the side being moved towards
+        let upper = if to_lower == to_upper {
+            value > self.upper
+        } else {
+            to_upper < to_lower
+        };
+        self.state = if upper {
+            SliderState::UpperSelected
+        } else {
+            SliderState::LowerSelected
+        };
+    }
+
+    /// Moves the selected handle towards `value`, following the slider's mode: the
+    /// handle stops at the other one when clamped, pushes it along in push mode, and
+    /// carries it along at the same distance in locked width mode
+    pub fn drag_selected_to(&mut self, value: T) {
+        if self.selected_value().is_none() {
+            return;
+        }
+        let value = partial_max(partial_min(value, self.max), self.min);
+        if let Err(SliderEntryError::CrossesOtherHandle) = self.set_selected(value) {
+            let stop = match self.state {
+                SliderState::LowerSelected => self.upper,
+                _ => self.lower,
+            };
+            let _ = self.set_selected(stop);
+        }
     }
 }
 
-impl Serialize for StatefulSlider {
+impl<T: SliderValue> Serialize for StatefulSlider<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -1,6 +1,9 @@
-use ratatui::widgets::*;
+use alloc::{vec, vec::Vec};
+use core::mem;
 use serde::{Serialize, Serializer};
 
+use crate::{TableSelection, TableState};
+
 /// Allows a snake-filled table of arbitrary size
 /// The final row does not need to be filled
 /// [ a , b , c , d ]
@@ -13,6 +16,8 @@ pub struct StatefulTable<T: Clone + Serialize> {
     pub items: Vec<Vec<Option<T>>>,
     pub rows: usize,
     pub cols: usize,
+    /// Number of columns the table was created with, which reflowing never exceeds
+    pub preferred_cols: usize,
 }
 
 // Size-related functions
@@ -27,16 +32,17 @@ impl<T: Clone + Serialize> StatefulTable<T> {
             })
             .sum()
     }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     pub fn full_len(&self) -> usize {
         self.rows * self.cols
     }
     pub fn as_vec(&self) -> Vec<T> {
         let mut v = Vec::with_capacity(self.len());
         for row in self.items.iter() {
-            for item in row.iter() {
-                if let Some(i) = item {
-                    v.push(i.clone());
-                }
+            for i in row.iter().flatten() {
+                v.push(i.clone());
             }
         }
         v
@@ -49,8 +55,9 @@ impl<T: Clone + Serialize> StatefulTable<T> {
         Self {
             state: TableState::default().with_selected(Some(TableSelection::default())),
             items: vec![vec![None; cols]; rows],
-            rows: rows,
-            cols: cols,
+            rows,
+            cols,
+            preferred_cols: cols,
         }
     }
     pub fn with_items(rows: usize, cols: usize, v: Vec<T>) -> Self {
@@ -79,10 +86,37 @@ impl<T: Clone + Serialize> StatefulTable<T> {
         self.state.select(Some(TableSelection::Cell { row, col }));
     }
 
+    /// Selects the filled cell nearest to (row, col), for positions saved against a table
+    /// which may have changed shape since. Does nothing if the table is empty.
+    pub fn select_nearest(&mut self, row: usize, col: usize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let target = (row.min(self.rows - 1), col.min(self.cols - 1));
+        // Take the last filled cell at or before the target, reading row by row,
+        // or the first filled cell if the table starts with gaps
+        let mut nearest = None;
+        for (row, items) in self.items.iter().enumerate() {
+            for (col, item) in items.iter().enumerate() {
+                if item.is_some() && (nearest.is_none() || (row, col) <= target) {
+                    nearest = Some((row, col));
+                }
+            }
+        }
+        if let Some((row, col)) = nearest {
+            self.select(row, col);
+        }
+    }
+
     pub fn get_selected(&mut self) -> Option<&mut T> {
         self.items[self.state.selected_row().unwrap()][self.state.selected_col().unwrap()].as_mut()
     }
 
+    pub fn selected_item(&self) -> Option<&T> {
+        self.get(self.state.selected_row()?, self.state.selected_col()?)
+    }
+
     pub fn get(&self, row: usize, column: usize) -> Option<&T> {
         if row >= self.rows || column >= self.cols {
             None
@@ -221,6 +255,48 @@ impl<T: Clone + Serialize> StatefulTable<T> {
     }
 }
 
+// Layout Functions
+impl<T: Clone + Serialize> StatefulTable<T> {
+    /// Rearranges the items into `cols` columns, up to the preferred number of columns,
+    /// keeping the same item selected. The number of rows grows or shrinks to fit the items.
+    /// Returns whether the table changed shape.
+    ///
+    /// For example, reflowing a 2x3 table with 5 elements into 2 columns:
+    ///
+    /// [ a , [b],  c ]        [ a , [b]]
+    /// [ d ,  e ,    ]  --->  [ c ,  d ]
+    ///                        [ e ,    ]
+    pub fn reflow(&mut self, cols: usize) -> bool {
+        let cols = cols.clamp(1, self.preferred_cols.max(1));
+        if cols == self.cols {
+            return false;
+        }
+        // Gaps are dropped, so the selection is tracked by its position among the items
+        let selected = self.state.selected_row().zip(self.state.selected_col());
+        let mut selected_idx = 0;
+        let mut items = Vec::with_capacity(self.len());
+        for (row, cells) in mem::take(&mut self.items).into_iter().enumerate() {
+            for (col, item) in cells.into_iter().enumerate() {
+                if let Some(item) = item {
+                    if selected.is_some_and(|selected| (row, col) <= selected) {
+                        selected_idx = items.len();
+                    }
+                    items.push(item);
+                }
+            }
+        }
+        self.rows = items.len().div_ceil(cols).max(1);
+        self.cols = cols;
+        self.items = vec![vec![None; cols]; self.rows];
+        for (idx, item) in items.into_iter().enumerate() {
+            self.items[idx / cols][idx % cols] = Some(item);
+        }
+        self.state = TableState::default();
+        self.select(selected_idx / cols, selected_idx % cols);
+        true
+    }
+}
+
 impl<T: Clone + Serialize> Serialize for StatefulTable<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -246,7 +322,7 @@ impl<'a, T: Clone + Serialize> Iterator for StatefulTableIterator<'a, T> {
 }
 
 impl<T: Clone + Serialize> StatefulTable<T> {
-    pub fn iter(&self) -> StatefulTableIterator<T> {
+    pub fn iter(&self) -> StatefulTableIterator<'_, T> {
         StatefulTableIterator {
             stateful_table: self,
             index: 0,
@@ -255,7 +331,7 @@ impl<T: Clone + Serialize> StatefulTable<T> {
 }
 
 pub struct StatefulTableIteratorMut<'a, T: Clone + Serialize> {
-    inner: std::iter::Flatten<std::slice::IterMut<'a, Vec<Option<T>>>>,
+    inner: core::iter::Flatten<core::slice::IterMut<'a, Vec<Option<T>>>>,
 }
 
 impl<'a, T: Clone + Serialize> Iterator for StatefulTableIteratorMut<'a, T> {
@@ -266,7 +342,7 @@ impl<'a, T: Clone + Serialize> Iterator for StatefulTableIteratorMut<'a, T> {
 }
 
 impl<'a, T: Clone + Serialize + 'a> StatefulTable<T> {
-    pub fn iter_mut(&'a mut self) -> StatefulTableIteratorMut<T> {
+    pub fn iter_mut(&'a mut self) -> StatefulTableIteratorMut<'a, T> {
         StatefulTableIteratorMut {
             inner: self.items.iter_mut().flatten(),
         }
@@ -0,0 +1,59 @@
+use ratatui::layout::Rect;
+
+/// Where things were drawn by the last call to `render_ui`, so that mouse positions
+/// can be mapped back to what is under them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UiLayout {
+    /// Each visible tab title, with the index of its tab
+    pub tabs: Vec<(usize, Rect)>,
+    /// Each visible cell of the submenu or toggle grid, with its row and column in the menu
+    pub cells: Vec<(usize, usize, Rect)>,
+    /// The area inside the slider's border, if a slider page is shown
+    pub slider: Option<Rect>,
+}
+
+/// What a mouse position is over
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HitTarget {
+    Tab(usize),
+    Cell {
+        row: usize,
+        col: usize,
+    },
+    /// A position along the slider, from 0.0 at the minimum to 1.0 at the maximum
+    Slider(f64),
+}
+
+impl UiLayout {
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<HitTarget> {
+        if let Some((idx, _)) = self.tabs.iter().find(|(_, area)| contains(area, x, y)) {
+            return Some(HitTarget::Tab(*idx));
+        }
+        if let Some((row, col, _)) = self.cells.iter().find(|(_, _, area)| contains(area, x, y)) {
+            return Some(HitTarget::Cell {
+                row: *row,
+                col: *col,
+            });
+        }
+        match self.slider {
+            Some(area) if contains(&area, x, y) => Some(HitTarget::Slider(self.slider_fraction(x))),
+            _ => None,
+        }
+    }
+
+    /// Position along the slider for a column, clamped to the ends of the track.
+    /// This is used while dragging, when the mouse may have left the slider.
+    pub fn slider_fraction(&self, x: u16) -> f64 {
+        match self.slider {
+            Some(area) if area.width > 1 => {
+                let offset = x.saturating_sub(area.x).min(area.width - 1);
+                offset as f64 / (area.width - 1) as f64
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+fn contains(area: &Rect, x: u16, y: u16) -> bool {
+    area.x <= x && x < area.x + area.width && area.y <= y && y < area.y + area.height
+}
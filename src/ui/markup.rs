@@ -0,0 +1,134 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::{Button, Theme};
+
+const BULLET: &str = "• ";
+
+/// Parses help text with light markup into styled lines.
+///
+/// - `**bold**` toggles bold text
+/// - `[red]coloured[/]` colours text with any colour ratatui can parse, and can be nested
+/// - Lines starting with `- ` or `* ` become bullet points, keeping their indentation
+/// - `<A>`, `<ZL>`, `<Start>` and the like are drawn as button glyphs
+/// - `\` escapes the next character, e.g. `\[` or `\*`
+///
+/// Anything which doesn't parse as markup, such as `[not a colour]`, is kept as plain text.
+pub fn parse_markup(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    text.lines().map(|line| parse_line(line, theme)).collect()
+}
+
+/// Button names which can appear between angle brackets
+fn button_label(name: &str) -> Option<&'static str> {
+    match name {
+        "-" => Some("-"),
+        _ if name.eq_ignore_ascii_case("select") || name.eq_ignore_ascii_case("minus") => Some("-"),
+        _ => Button::from_name(name).map(|button| button.glyph()),
+    }
+}
+
+struct LineBuilder<'t> {
+    theme: &'t Theme,
+    spans: Vec<Span<'static>>,
+    current: String,
+    bold: bool,
+    colours: Vec<Color>,
+}
+
+impl<'t> LineBuilder<'t> {
+    fn style(&self) -> Style {
+        let mut style = self.theme.text;
+        if let Some(colour) = self.colours.last() {
+            style = style.fg(*colour);
+        }
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        style
+    }
+
+    /// Ends the current run of text, so that the next one can have a different style
+    fn flush(&mut self) {
+        if !self.current.is_empty() {
+            let text = std::mem::take(&mut self.current);
+            self.spans.push(Span::styled(text, self.style()));
+        }
+    }
+}
+
+fn parse_line(line: &str, theme: &Theme) -> Line<'static> {
+    let mut builder = LineBuilder {
+        theme,
+        spans: vec![],
+        current: String::new(),
+        bold: false,
+        colours: vec![],
+    };
+
+    let trimmed = line.trim_start();
+    let mut rest = match trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        Some(item) => {
+            builder
+                .current
+                .push_str(&line[..line.len() - trimmed.len()]);
+            builder.current.push_str(BULLET);
+            item
+        }
+        None => line,
+    };
+
+    while let Some(ch) = rest.chars().next() {
+        if ch == '\\' {
+            // Keep the escaped character as is
+            rest = &rest[1..];
+            if let Some(escaped) = rest.chars().next() {
+                builder.current.push(escaped);
+                rest = &rest[escaped.len_utf8()..];
+            }
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            builder.flush();
+            builder.bold = !builder.bold;
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("[/]") {
+            builder.flush();
+            builder.colours.pop();
+            rest = after;
+            continue;
+        }
+        if ch == '[' {
+            if let Some(end) = rest.find(']') {
+                if let Ok(colour) = rest[1..end].parse::<Color>() {
+                    builder.flush();
+                    builder.colours.push(colour);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        if ch == '<' {
+            if let Some(end) = rest.find('>') {
+                if let Some(label) = button_label(&rest[1..end]) {
+                    builder.flush();
+                    builder
+                        .spans
+                        .push(Span::styled(format!(" {} ", label), theme.button));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+        builder.current.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    builder.flush();
+    Line::from(builder.spans)
+}
@@ -0,0 +1,634 @@
+use crate::{
+    App, AppView, EntryView, FooterView, GridView, HelpView, HintView, Keymap, PageView,
+    SliderView, SubMenuType, SubmenuCell, TableSelection, TableState, ToggleCell,
+};
+use ratatui::{layout::Rect, prelude::*, widgets::*, Frame};
+
+mod hit_test;
+pub use hit_test::*;
+mod markup;
+pub use markup::*;
+mod slider;
+pub use slider::*;
+mod snapshot;
+pub use snapshot::*;
+mod state;
+use state::render_table;
+pub use state::UiState;
+mod symbols;
+pub use symbols::*;
+mod tabs;
+pub use tabs::*;
+mod theme;
+pub use theme::*;
+
+/// Below this size, a "terminal too small" screen is shown instead of the menu
+pub const MIN_TERMINAL_WIDTH: u16 = 32;
+pub const MIN_TERMINAL_HEIGHT: u16 = 12;
+
+/// Space reserved on either side of the tab bar for the scroll markers
+const TAB_MARKER_WIDTH: u16 = 2;
+
+/// Gap between table columns, matching ratatui's default column spacing
+const COLUMN_SPACING: usize = 1;
+
+/// Draws the menu, returning where everything was drawn so that mouse input can be hit-tested.
+/// The menu is only read; what the renderer needs to remember between frames is kept in
+/// `ui_state`.
+pub fn render_ui(frame: &mut Frame, app: &App, ui_state: &mut UiState) -> UiLayout {
+    let mut ui_layout = UiLayout::default();
+    ui_state.columns = 0;
+    // Cloned so that the rest of the state can be updated while drawing
+    let theme = &ui_state.theme().clone();
+    let size = frame.size();
+    frame.render_widget(Block::default().style(theme.background), size);
+    if size.width < MIN_TERMINAL_WIDTH || size.height < MIN_TERMINAL_HEIGHT {
+        render_too_small(frame, theme, size);
+        return ui_layout;
+    }
+
+    let view = app.view();
+    let hint_lines = hint_lines(&view.hints, theme, size.width);
+
+    // Set up Layout
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(hint_lines.len() as u16),
+        ])
+        .split(size);
+
+    // Define Areas
+    // tab_area: list across the top
+    // menu_area: menu entries
+    // help_area: help text for the selected entry
+    // hint_area: what each button does right now
+    let tab_area = layout[0];
+    let menu_area = layout[1];
+    let help_area = layout[2];
+    let hint_area = layout[3];
+
+    render_tabs(frame, &view, theme, &mut ui_layout, tab_area);
+    match &view.page {
+        PageView::Submenus(grid) => {
+            let title = view.breadcrumbs.join(" > ");
+            render_submenu_page(
+                frame,
+                grid,
+                title,
+                theme,
+                ui_state,
+                &mut ui_layout,
+                menu_area,
+            )
+        }
+        PageView::Toggles { submenu_type, grid } => render_toggle_page(
+            frame,
+            grid,
+            *submenu_type,
+            theme,
+            ui_state,
+            &mut ui_layout,
+            menu_area,
+        ),
+        PageView::Slider(slider) => {
+            render_slider_page(frame, slider, theme, &mut ui_layout, menu_area)
+        }
+        PageView::Confirmation { prompt } => {
+            render_confirmation_page(frame, prompt, theme, menu_area)
+        }
+        PageView::Closed => {}
+    }
+    render_footer(frame, &view.footer, theme, help_area);
+    frame.render_widget(Paragraph::new(hint_lines), hint_area);
+    if let Some(help) = &view.help {
+        ui_state.help_scroll = render_help_overlay(frame, help, theme, menu_area);
+    }
+    ui_layout
+}
+
+/// The table state to draw a grid with, scrolled to where it was last frame
+fn grid_state<C>(grid: &GridView<C>, ui_state: &UiState) -> TableState {
+    TableState::default()
+        .with_selected(
+            grid.selected
+                .map(|(row, col)| TableSelection::Cell { row, col }),
+        )
+        .with_offset(ui_state.grid_offset)
+}
+
+/// Number of columns `cell_width` wide the grid has room for, up to its preferred number
+fn fitted_columns<C>(grid: &GridView<C>, cell_width: usize, area: Rect) -> usize {
+    columns_that_fit(area, cell_width).clamp(1, grid.preferred_cols.max(1))
+}
+
+/// Lays the cells of a grid out in table rows, each in its own row and column
+fn grid_rows<'c, C, F>(
+    grid: &'c GridView<C>,
+    position: fn(&C) -> (usize, usize),
+    cell: F,
+) -> Vec<Vec<Cell<'c>>>
+where
+    F: Fn(&'c C) -> Cell<'c>,
+{
+    let mut rows: Vec<Vec<Cell>> = (0..grid.rows)
+        .map(|_| vec![Cell::from(""); grid.cols])
+        .collect();
+    for c in grid.cells.iter() {
+        let (row, col) = position(c);
+        rows[row][col] = cell(c);
+    }
+    rows
+}
+
+fn render_submenu_page(
+    frame: &mut Frame,
+    grid: &GridView<SubmenuCell>,
+    title: String,
+    theme: &Theme,
+    ui_state: &mut UiState,
+    ui_layout: &mut UiLayout,
+    area: Rect,
+) {
+    let cell_width = grid
+        .cells
+        .iter()
+        .map(|submenu| submenu.title.chars().count())
+        .max()
+        .unwrap_or(0);
+    let column_width = column_width(area, grid.cols);
+    // Each cell shows the title, with a summary of the current value underneath
+    let rows = grid_rows(
+        grid,
+        |c| (c.row, c.col),
+        |submenu| {
+            Cell::from(vec![
+                Line::from(truncate(&submenu.title, column_width)),
+                Line::from(Span::styled(
+                    truncate(&submenu.summary, column_width),
+                    theme.summary,
+                )),
+            ])
+        },
+    )
+    .into_iter()
+    .map(|cells| Row::new(cells).height(2));
+
+    let widths = column_widths(grid.cols);
+    let table = Table::new(rows)
+        .block(themed_block(theme).title(title))
+        .style(theme.text)
+        .cell_highlight_style(theme.highlight)
+        .widths(&widths);
+
+    let mut state = grid_state(grid, ui_state);
+    render_table(frame, table, area, &mut state);
+    ui_state.grid_offset = state.offset();
+    ui_state.columns = fitted_columns(grid, cell_width, area);
+    let positions = grid.cells.iter().map(|c| (c.row, c.col));
+    record_cells(ui_layout, positions, grid.cols, state.offset(), area, 2);
+}
+
+fn render_toggle_page(
+    frame: &mut Frame,
+    grid: &GridView<ToggleCell>,
+    submenu_type: SubMenuType,
+    theme: &Theme,
+    ui_state: &mut UiState,
+    ui_layout: &mut UiLayout,
+    area: Rect,
+) {
+    let symbols = theme.symbols;
+    let has_levels = grid.cells.iter().any(|toggle| toggle.max > 1);
+
+    // The legend explaining the glyphs sits below the grid
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+    let (area, legend_area) = (layout[0], layout[1]);
+    let legend = toggle_legend(submenu_type, has_levels, &symbols);
+    frame.render_widget(Paragraph::new(legend).style(theme.help), legend_area);
+
+    // Display both the title and the value
+    let text = |t: &ToggleCell| toggle_text(&t.title, t.value, t.max, submenu_type, &symbols);
+    let cell_width = grid
+        .cells
+        .iter()
+        .map(|t| text(t).chars().count())
+        .max()
+        .unwrap_or(0);
+    let rows: Vec<Row> = grid_rows(grid, |c| (c.row, c.col), |t| Cell::from(text(t)))
+        .into_iter()
+        .map(Row::new)
+        .collect();
+
+    let widths = column_widths(grid.cols);
+    let table = Table::new(rows)
+        .block(themed_block(theme).title("Submenus:"))
+        .style(theme.text)
+        .cell_highlight_style(theme.highlight)
+        .widths(&widths);
+
+    let mut state = grid_state(grid, ui_state);
+    render_table(frame, table, area, &mut state);
+    ui_state.grid_offset = state.offset();
+    ui_state.columns = fitted_columns(grid, cell_width, area);
+    let positions = grid.cells.iter().map(|c| (c.row, c.col));
+    record_cells(ui_layout, positions, grid.cols, state.offset(), area, 1);
+}
+
+fn render_slider_page(
+    frame: &mut Frame,
+    slider: &SliderView,
+    theme: &Theme,
+    ui_layout: &mut UiLayout,
+    area: Rect,
+) {
+    let widget = RangeSlider::new(slider)
+        .block(themed_block(theme).title(slider.title.as_str()))
+        .track_style(theme.slider_track)
+        .range_style(theme.slider_range)
+        .hover_style(theme.slider_hover)
+        .selected_style(theme.slider_selected)
+        .label_style(theme.slider_label);
+    let slider_area = match &slider.entry {
+        Some(entry) => {
+            // The entry box sits below the slider while a value is being entered
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(4)])
+                .split(area);
+            render_numeric_entry(frame, entry, theme, layout[1]);
+            layout[0]
+        }
+        None => area,
+    };
+    frame.render_widget(widget, slider_area);
+    ui_layout.slider = Some(inner(slider_area));
+}
+
+fn render_numeric_entry(frame: &mut Frame, entry: &EntryView, theme: &Theme, area: Rect) {
+    let spans: Vec<Span> = entry
+        .glyphs
+        .iter()
+        .map(|glyph| {
+            let style = if glyph.cursor {
+                theme.entry_cursor
+            } else {
+                theme.text
+            };
+            Span::styled(glyph.text.as_str(), style)
+        })
+        .collect();
+    let mut text = vec![Line::from(spans)];
+    if let Some(error) = &entry.error {
+        text.push(Line::from(Span::styled(error.as_str(), theme.error)));
+    }
+    let paragraph = Paragraph::new(text)
+        .block(themed_block(theme).title("Enter Value"))
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_tabs(
+    frame: &mut Frame,
+    view: &AppView,
+    theme: &Theme,
+    ui_layout: &mut UiLayout,
+    area: Rect,
+) {
+    let selected = view.selected_tab;
+    let count = view.tabs.len();
+    // Position indicator on the right, e.g. "3/6"
+    let indicator = format!("{}/{}", selected + 1, count);
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(TAB_MARKER_WIDTH),
+            Constraint::Min(0),
+            Constraint::Length(TAB_MARKER_WIDTH),
+            Constraint::Length(indicator.len() as u16 + 1),
+        ])
+        .split(area);
+
+    let title_widths: Vec<usize> = view
+        .tabs
+        .iter()
+        .map(|tab| tab.title.chars().count())
+        .collect();
+    let visible = visible_tabs(&title_widths, selected, layout[1].width as usize);
+    let titles: Vec<&str> = view.tabs[visible.clone()]
+        .iter()
+        .map(|tab| tab.title.as_str())
+        .collect();
+    let tabs = Tabs::new(titles)
+        .select(selected - visible.start)
+        .style(theme.tab)
+        .highlight_style(theme.tab_selected);
+    frame.render_widget(tabs, layout[1]);
+
+    // Each title is padded by a cell on either side, followed by a one cell divider
+    let mut x = layout[1].x;
+    for idx in visible.clone() {
+        let width = (title_widths[idx] as u16 + 2).min(layout[1].right().saturating_sub(x));
+        ui_layout
+            .tabs
+            .push((idx, Rect::new(x, layout[1].y, width, 1)));
+        x = x.saturating_add(width + 1);
+    }
+
+    // Markers show that there are more tabs off either end of the bar
+    if visible.start > 0 {
+        frame.render_widget(Paragraph::new("<").style(theme.tab), layout[0]);
+    }
+    if visible.end < count {
+        frame.render_widget(
+            Paragraph::new(">")
+                .style(theme.tab)
+                .alignment(Alignment::Right),
+            layout[2],
+        );
+    }
+    frame.render_widget(
+        Paragraph::new(indicator)
+            .style(theme.tab)
+            .alignment(Alignment::Right),
+        layout[3],
+    );
+}
+
+fn render_confirmation_page(frame: &mut Frame, prompt: &str, theme: &Theme, area: Rect) {
+    let text = vec![
+        Line::from(prompt),
+        Line::from(""),
+        Line::from("A: Confirm    B: Cancel"),
+    ];
+    let paragraph = Paragraph::new(text)
+        .block(themed_block(theme).title("Confirmation"))
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
+
+fn render_footer(frame: &mut Frame, footer: &FooterView, theme: &Theme, area: Rect) {
+    match footer {
+        FooterView::Message(message) => {
+            frame.render_widget(Paragraph::new(message.as_str()).style(theme.message), area);
+        }
+        FooterView::Help(help_text) => {
+            let paragraph = Paragraph::new(parse_markup(help_text, theme))
+                .style(theme.help)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(paragraph, area);
+        }
+    }
+}
+
+/// Lays out the button hints in as many lines as they need, without splitting a hint.
+/// Neighbouring buttons which do the same thing share a hint, e.g. "←/→ Switch handle".
+fn hint_lines(hints: &[HintView], theme: &Theme, width: u16) -> Vec<Line<'static>> {
+    let mut grouped: Vec<(String, &str)> = vec![];
+    for hint in hints {
+        match grouped.last_mut() {
+            Some((label, action)) if *action == hint.action => {
+                label.push('/');
+                label.push_str(&hint.label);
+            }
+            _ => grouped.push((hint.label.clone(), &hint.action)),
+        }
+    }
+    let mut lines = vec![Line::default()];
+    for (label, action) in grouped {
+        let label = Span::styled(format!(" {} ", label), theme.button);
+        let action = Span::styled(format!(" {}  ", action), theme.help);
+        // The gap after the last hint on a line doesn't need to fit
+        let hint_width = label.width() + action.width() - 2;
+        let line_width = lines.last().map_or(0, Line::width);
+        if line_width > 0 && line_width + hint_width > width as usize {
+            lines.push(Line::default());
+        }
+        let line = lines.last_mut().expect("There is always a line");
+        line.spans.push(label);
+        line.spans.push(action);
+    }
+    lines
+}
+
+/// Draws the help overlay, returning its scroll position clamped to the length of the text
+fn render_help_overlay(frame: &mut Frame, help: &HelpView, theme: &Theme, area: Rect) -> u16 {
+    // The overlay covers the middle of the menu, leaving a margin around it
+    let overlay = Rect {
+        x: area.x + area.width / 10,
+        y: area.y + area.height / 10,
+        width: area.width - area.width / 5,
+        height: area.height - area.height / 5,
+    };
+    let block = themed_block(theme).title(format!("Help: {}", help.title));
+    let inner = block.inner(overlay);
+    let lines = parse_markup(&help.text, theme);
+
+    // Stop scrolling once the last line is at the bottom of the overlay
+    let line_count: usize = lines
+        .iter()
+        .map(|line| wrapped_height(line.width(), inner.width as usize))
+        .sum();
+    let max_scroll = line_count.saturating_sub(inner.height as usize) as u16;
+    let scroll = help.scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .style(theme.text)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(paragraph, overlay);
+    scroll
+}
+
+/// Draws the list of key bindings over the middle of the screen.
+/// Frontends which read the keyboard draw this after `render_ui` while the list is open.
+pub fn render_keymap_overlay(frame: &mut Frame, keymap: &Keymap, theme: &Theme) {
+    let size = frame.size();
+    let entries = keymap.entries();
+    let name_width = entries
+        .iter()
+        .map(|(action, _)| action.name().chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = entries
+        .iter()
+        .map(|(action, keys)| {
+            let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+            let keys = if keys.is_empty() {
+                "(unbound)".to_string()
+            } else {
+                keys.join(", ")
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$} ", action.name(), width = name_width),
+                    theme.button,
+                ),
+                Span::styled(format!(" {}", keys), theme.text),
+            ])
+        })
+        .collect();
+    let width = lines
+        .iter()
+        .map(|line| line.width() as u16 + 2)
+        .max()
+        .unwrap_or(0)
+        .max(24)
+        .min(size.width);
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let overlay = Rect {
+        x: size.x + (size.width - width) / 2,
+        y: size.y + (size.height - height) / 2,
+        width,
+        height,
+    };
+    let paragraph = Paragraph::new(lines)
+        .block(themed_block(theme).title(format!("Keys: {}", keymap.name)))
+        .style(theme.text);
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(paragraph, overlay);
+}
+
+/// Approximate number of rows a line takes up once wrapped to `width`
+fn wrapped_height(line_width: usize, width: usize) -> usize {
+    if width == 0 {
+        return 0;
+    }
+    line_width.div_ceil(width).max(1)
+}
+
+fn render_too_small(frame: &mut Frame, theme: &Theme, area: Rect) {
+    let text = vec![
+        Line::from("Terminal too small"),
+        Line::from(format!(
+            "Need {}x{}, have {}x{}",
+            MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT, area.width, area.height
+        )),
+    ];
+    let paragraph = Paragraph::new(text)
+        .style(theme.error)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// The area inside a block with borders on all sides
+fn inner(area: Rect) -> Rect {
+    Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    }
+}
+
+/// Records where each visible cell of a grid `cols` wide was drawn inside a bordered block,
+/// given the cells' rows and columns in the menu
+fn record_cells(
+    ui_layout: &mut UiLayout,
+    positions: impl Iterator<Item = (usize, usize)>,
+    cols: usize,
+    offset: usize,
+    area: Rect,
+    row_height: u16,
+) {
+    let inner = inner(area);
+    let width = column_width(area, cols) as u16;
+    for (row, col) in positions {
+        if row < offset {
+            continue;
+        }
+        let y = inner.y + (row - offset) as u16 * row_height;
+        if y + row_height > inner.bottom() {
+            continue;
+        }
+        let x = inner.x + col as u16 * (width + COLUMN_SPACING as u16);
+        ui_layout
+            .cells
+            .push((row, col, Rect::new(x, y, width, row_height)));
+    }
+}
+
+fn themed_block(theme: &Theme) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+}
+
+/// A toggle's title with a glyph showing its value: pips for toggles with several levels,
+/// otherwise a radio button for single-choice submenus or a checkbox for multiple-choice ones
+fn toggle_text(
+    title: &str,
+    value: u8,
+    max: u8,
+    submenu_type: SubMenuType,
+    symbols: &SymbolSet,
+) -> String {
+    let on = value > 0;
+    let glyph = match submenu_type {
+        _ if max > 1 => symbols.pips(value, max),
+        SubMenuType::ToggleSingle if on => symbols.radio_on.to_string(),
+        SubMenuType::ToggleSingle => symbols.radio_off.to_string(),
+        _ if on => symbols.checkbox_on.to_string(),
+        _ => symbols.checkbox_off.to_string(),
+    };
+    format!("{} {}", glyph, title)
+}
+
+fn toggle_legend(submenu_type: SubMenuType, has_levels: bool, symbols: &SymbolSet) -> String {
+    let mut legend = match submenu_type {
+        SubMenuType::ToggleSingle => format!(
+            "{} Selected  {} Not selected  (choose one)",
+            symbols.radio_on, symbols.radio_off
+        ),
+        _ => format!(
+            "{} On  {} Off  (choose any)",
+            symbols.checkbox_on, symbols.checkbox_off
+        ),
+    };
+    if has_levels {
+        legend += &format!("  {}{} Level", symbols.pip_on, symbols.pip_off);
+    }
+    legend
+}
+
+/// Number of columns `cell_width` wide which fit inside a bordered block
+fn columns_that_fit(area: Rect, cell_width: usize) -> usize {
+    let inner_width = area.width.saturating_sub(2) as usize;
+    (inner_width + COLUMN_SPACING) / (cell_width + COLUMN_SPACING)
+}
+
+/// Width of each of `cols` equal columns inside a bordered block
+fn column_width(area: Rect, cols: usize) -> usize {
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let spacing = COLUMN_SPACING * cols.saturating_sub(1);
+    inner_width.saturating_sub(spacing) / cols.max(1)
+}
+
+/// Shortens text to fit `width` cells, marking the cut with an ellipsis
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else if width == 0 {
+        String::new()
+    } else {
+        text.chars()
+            .take(width - 1)
+            .chain(std::iter::once('…'))
+            .collect()
+    }
+}
+
+fn column_widths(cols: usize) -> Vec<Constraint> {
+    vec![Constraint::Ratio(1, cols as u32); cols]
+}
@@ -0,0 +1,386 @@
+use core::marker::PhantomData;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, StatefulWidget, Widget},
+};
+
+use crate::{SliderMode, SliderState, SliderValue, SliderView, StatefulSlider};
+
+const TRACK_SYMBOL_HORIZONTAL: &str = "─";
+const RANGE_SYMBOL_HORIZONTAL: &str = "━";
+const TRACK_SYMBOL_VERTICAL: &str = "│";
+const RANGE_SYMBOL_VERTICAL: &str = "┃";
+const HANDLE_SYMBOL: &str = "█";
+const TICK_SYMBOL: &str = "^";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliderOrientation {
+    Horizontal,
+    Vertical,
+}
+
+/// Renders a slider's view as a track with a handle at each end of the selected range.
+/// A StatefulSlider can also be drawn directly as the state of `RangeSlider::default().stateful()`.
+///
+/// Horizontal sliders draw the track on the first row, the value labels on the second,
+/// and any ticks on the two rows below that.
+/// Vertical sliders run from the minimum at the bottom to the maximum at the top,
+/// with the labels to the right of the track.
+#[derive(Clone)]
+pub struct RangeSlider<'b> {
+    view: Option<&'b SliderView>,
+    block: Option<Block<'b>>,
+    orientation: SliderOrientation,
+    track_style: Style,
+    range_style: Style,
+    hover_style: Style,
+    selected_style: Style,
+    label_style: Style,
+}
+
+impl<'b> Default for RangeSlider<'b> {
+    /// A slider without a view, to be drawn with a StatefulSlider as its state
+    fn default() -> RangeSlider<'b> {
+        RangeSlider {
+            view: None,
+            block: None,
+            orientation: SliderOrientation::Horizontal,
+            track_style: Style::default().fg(Color::White),
+            range_style: Style::default().fg(Color::Yellow),
+            hover_style: Style::default().fg(Color::Red),
+            selected_style: Style::default().fg(Color::Green),
+            label_style: Style::default(),
+        }
+    }
+}
+
+impl<'b> RangeSlider<'b> {
+    pub fn new(view: &'b SliderView) -> RangeSlider<'b> {
+        RangeSlider {
+            view: Some(view),
+            ..RangeSlider::default()
+        }
+    }
+
+    /// Draws a StatefulSlider rather than a view, with the same block and styles
+    pub fn stateful<T: SliderValue>(self) -> StatefulRangeSlider<'b, T> {
+        StatefulRangeSlider {
+            slider: self,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn block(mut self, block: Block<'b>) -> RangeSlider<'b> {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn orientation(mut self, orientation: SliderOrientation) -> RangeSlider<'b> {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Style of the track outside of the selected range
+    pub fn track_style(mut self, style: Style) -> RangeSlider<'b> {
+        self.track_style = style;
+        self
+    }
+
+    /// Style of the track between the handles, and of handles which aren't hovered or selected
+    pub fn range_style(mut self, style: Style) -> RangeSlider<'b> {
+        self.range_style = style;
+        self
+    }
+
+    pub fn hover_style(mut self, style: Style) -> RangeSlider<'b> {
+        self.hover_style = style;
+        self
+    }
+
+    pub fn selected_style(mut self, style: Style) -> RangeSlider<'b> {
+        self.selected_style = style;
+        self
+    }
+
+    /// Style of the minimum, maximum and tick labels
+    pub fn label_style(mut self, style: Style) -> RangeSlider<'b> {
+        self.label_style = style;
+        self
+    }
+
+    fn handle_style(&self, slider: &SliderView, is_lower: bool) -> Style {
+        // In locked-width mode, selecting either handle moves both
+        let locked = slider.mode == SliderMode::LockedWidth;
+        match slider.state {
+            SliderState::RangeHover => self.hover_style,
+            SliderState::RangeSelected => self.selected_style,
+            SliderState::LowerHover if is_lower => self.hover_style,
+            SliderState::UpperHover if !is_lower => self.hover_style,
+            SliderState::LowerSelected | SliderState::UpperSelected if locked => {
+                self.selected_style
+            }
+            SliderState::LowerSelected if is_lower => self.selected_style,
+            SliderState::UpperSelected if !is_lower => self.selected_style,
+            _ => self.range_style,
+        }
+    }
+
+    /// Style of the track between the handles, which follows the handles
+    /// when the range is moved as one
+    fn range_track_style(&self, slider: &SliderView) -> Style {
+        let locked = slider.mode == SliderMode::LockedWidth;
+        match slider.state {
+            SliderState::RangeHover => self.hover_style,
+            SliderState::RangeSelected => self.selected_style,
+            SliderState::LowerSelected | SliderState::UpperSelected if locked => {
+                self.selected_style
+            }
+            _ => self.range_style,
+        }
+    }
+
+    fn render_horizontal(&self, area: Rect, buf: &mut Buffer, slider: &SliderView) {
+        let length = area.width;
+        let lower_pos = offset(slider.lower, length);
+        let upper_pos = offset(slider.upper, length);
+        let lower_style = self.handle_style(slider, true);
+        let upper_style = self.handle_style(slider, false);
+        let range_style = self.range_track_style(slider);
+
+        // Track
+        for i in 0..length {
+            let in_range = lower_pos <= i && i <= upper_pos;
+            let (symbol, style) = if in_range {
+                (RANGE_SYMBOL_HORIZONTAL, range_style)
+            } else {
+                (TRACK_SYMBOL_HORIZONTAL, self.track_style)
+            };
+            buf.get_mut(area.x + i, area.y)
+                .set_symbol(symbol)
+                .set_style(style);
+        }
+
+        // Handles. The upper handle is drawn last unless the lower one is active,
+        // so that the active handle stays visible when both share a cell.
+        let mut handles = [(lower_pos, lower_style), (upper_pos, upper_style)];
+        if matches!(
+            slider.state,
+            SliderState::LowerHover | SliderState::LowerSelected
+        ) {
+            handles.reverse();
+        }
+        for (pos, style) in handles {
+            buf.get_mut(area.x + pos, area.y)
+                .set_symbol(HANDLE_SYMBOL)
+                .set_style(style);
+        }
+
+        if area.height < 2 {
+            return;
+        }
+
+        // Value labels are centred under their handles.
+        // If they would collide, they are placed side by side around the middle of the range.
+        let lower_text = slider.lower_label.clone();
+        let upper_text = slider.upper_label.clone();
+        let lower_width = text_width(&lower_text);
+        let upper_width = text_width(&upper_text);
+        let centred = |anchor: u16, width: u16| -> u16 {
+            anchor
+                .saturating_sub(width / 2)
+                .min(length.saturating_sub(width))
+        };
+        let mut lower_start = centred(lower_pos, lower_width);
+        let mut upper_start = centred(upper_pos, upper_width);
+        if lower_start + lower_width + 1 > upper_start {
+            let middle = (lower_pos + upper_pos) / 2;
+            lower_start = centred(middle, lower_width + 1 + upper_width);
+            upper_start = lower_start + lower_width + 1;
+        }
+        let mut labels = vec![
+            (lower_start, lower_text, lower_style),
+            (upper_start, upper_text, upper_style),
+        ];
+
+        // The limits are only labelled where they don't collide with the value labels
+        let min_text = slider.min_label.clone();
+        if text_width(&min_text) < lower_start {
+            labels.push((0, min_text, self.label_style));
+        }
+        let max_text = slider.max_label.clone();
+        let max_start = length.saturating_sub(text_width(&max_text));
+        if max_start > upper_start + upper_width {
+            labels.push((max_start, max_text, self.label_style));
+        }
+
+        for (start, text, style) in labels {
+            if start < length {
+                let width = (length - start) as usize;
+                buf.set_stringn(area.x + start, area.y + 1, text, width, style);
+            }
+        }
+
+        if area.height < 4 {
+            return;
+        }
+
+        // Ticks are assumed to be in ascending order.
+        // A label which would overlap the previous one is skipped, but its marker is still drawn.
+        let mut next_free = 0;
+        for tick in slider.ticks.iter() {
+            let pos = offset(tick.position, length);
+            buf.get_mut(area.x + pos, area.y + 2)
+                .set_symbol(TICK_SYMBOL)
+                .set_style(self.label_style);
+            if pos >= next_free {
+                let width = (length - pos) as usize;
+                buf.set_stringn(
+                    area.x + pos,
+                    area.y + 3,
+                    &tick.label,
+                    width,
+                    self.label_style,
+                );
+                next_free = pos + text_width(&tick.label) + 1;
+            }
+        }
+    }
+
+    fn render_vertical(&self, area: Rect, buf: &mut Buffer, slider: &SliderView) {
+        let length = area.height;
+        let bottom = area.y + length - 1;
+        let lower_row = bottom - offset(slider.lower, length);
+        let upper_row = bottom - offset(slider.upper, length);
+        let lower_style = self.handle_style(slider, true);
+        let upper_style = self.handle_style(slider, false);
+        let range_style = self.range_track_style(slider);
+
+        // Track
+        for y in area.y..=bottom {
+            let in_range = upper_row <= y && y <= lower_row;
+            let (symbol, style) = if in_range {
+                (RANGE_SYMBOL_VERTICAL, range_style)
+            } else {
+                (TRACK_SYMBOL_VERTICAL, self.track_style)
+            };
+            buf.get_mut(area.x, y).set_symbol(symbol).set_style(style);
+        }
+        for tick in slider.ticks.iter() {
+            let row = bottom - offset(tick.position, length);
+            buf.get_mut(area.x, row)
+                .set_symbol(TICK_SYMBOL)
+                .set_style(self.label_style);
+        }
+
+        // Handles, drawn as for horizontal sliders
+        let mut handles = [(lower_row, lower_style), (upper_row, upper_style)];
+        if matches!(
+            slider.state,
+            SliderState::LowerHover | SliderState::LowerSelected
+        ) {
+            handles.reverse();
+        }
+        for (row, style) in handles {
+            buf.get_mut(area.x, row)
+                .set_symbol(HANDLE_SYMBOL)
+                .set_style(style);
+        }
+
+        if area.width < 3 {
+            return;
+        }
+
+        // Labels sit to the right of the track, one per row.
+        // If both handles share a row, the upper label moves up a row, or the lower one down.
+        let label_x = area.x + 2;
+        let width = (area.width - 2) as usize;
+        let (mut lower_label_row, mut upper_label_row) = (lower_row, upper_row);
+        if lower_row == upper_row {
+            if upper_row > area.y {
+                upper_label_row -= 1;
+            } else if lower_row < bottom {
+                lower_label_row += 1;
+            }
+        }
+        let mut used_rows = vec![lower_label_row, upper_label_row];
+        let mut labels = vec![
+            (lower_label_row, slider.lower_label.clone(), lower_style),
+            (upper_label_row, slider.upper_label.clone(), upper_style),
+        ];
+        // The limits are only labelled where their rows are free
+        for (row, label) in [(bottom, &slider.min_label), (area.y, &slider.max_label)] {
+            if !used_rows.contains(&row) {
+                labels.push((row, label.clone(), self.label_style));
+                used_rows.push(row);
+            }
+        }
+        for tick in slider.ticks.iter() {
+            let row = bottom - offset(tick.position, length);
+            if !used_rows.contains(&row) {
+                labels.push((row, tick.label.clone(), self.label_style));
+                used_rows.push(row);
+            }
+        }
+
+        for (row, text, style) in labels {
+            buf.set_stringn(label_x, row, text, width, style);
+        }
+    }
+
+    fn render_view(mut self, area: Rect, buf: &mut Buffer, view: &SliderView) {
+        let slider_area = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+        if slider_area.width == 0 || slider_area.height == 0 {
+            return;
+        }
+        match self.orientation {
+            SliderOrientation::Horizontal => self.render_horizontal(slider_area, buf, view),
+            SliderOrientation::Vertical => self.render_vertical(slider_area, buf, view),
+        }
+    }
+}
+
+impl<'b> Widget for RangeSlider<'b> {
+    /// Draws the view the slider was created with, if any
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if let Some(view) = self.view {
+            self.render_view(area, buf, view);
+        }
+    }
+}
+
+/// A RangeSlider which draws a StatefulSlider, describing it afresh each time
+#[derive(Clone)]
+pub struct StatefulRangeSlider<'b, T> {
+    slider: RangeSlider<'b>,
+    _value: PhantomData<T>,
+}
+
+impl<'b, T: SliderValue> StatefulWidget for StatefulRangeSlider<'b, T> {
+    type State = StatefulSlider<T>;
+
+    fn render(self, area: Rect, buf: &mut Buffer, slider: &mut StatefulSlider<T>) {
+        // The title belongs to the block, so the view doesn't need one
+        let view = slider.view("");
+        self.slider.render_view(area, buf, &view);
+    }
+}
+
+/// Cell offset of a position along a track `length` cells long.
+/// The minimum is at the first cell and the maximum at the last.
+fn offset(fraction: f64, length: u16) -> u16 {
+    (fraction * length.saturating_sub(1) as f64).round() as u16
+}
+
+fn text_width(text: &str) -> u16 {
+    text.chars().count() as u16
+}
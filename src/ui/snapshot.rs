@@ -0,0 +1,31 @@
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+use crate::{render_ui, App, UiState};
+
+/// Renders the menu off-screen at a fixed size and returns it as plain text, one line per row.
+/// Styles are dropped and trailing spaces are trimmed, so the text can be compared against
+/// stored snapshots.
+pub fn render_to_text(app: &App, width: u16, height: u16) -> String {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("Could not create test terminal!");
+    terminal
+        .draw(|f| {
+            render_ui(f, app, &mut UiState::default());
+        })
+        .expect("Could not draw to test terminal!");
+    buffer_to_text(terminal.backend().buffer())
+}
+
+/// The symbols of a buffer as text, one line per row with trailing spaces trimmed
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let line: String = (area.left()..area.right())
+            .map(|x| buffer.get(x, y).symbol.as_str())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
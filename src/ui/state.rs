@@ -0,0 +1,114 @@
+use ratatui::widgets;
+
+use crate::{ListState, TableSelection, TableState, Theme};
+
+/// What the renderer keeps between frames. The menu itself is only read while drawing,
+/// so everything which depends on the terminal, such as its size and colours, is kept
+/// here instead.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiState {
+    /// The first row of the grid on display, so that scrolling carries over between frames
+    pub grid_offset: usize,
+    /// How many columns the grid on display has room for, or 0 if there isn't one.
+    /// Frontends pass this to `App::fit_columns` to reflow the grid to fit the terminal.
+    pub columns: usize,
+    /// The help overlay's scroll position, stopped at the end of the text.
+    /// Frontends can copy this back to `App::help_scroll` so that scrolling up again
+    /// starts from the last line rather than from wherever the player overshot to.
+    pub help_scroll: u16,
+    pub themes: Vec<Theme>,
+    pub selected_theme: usize,
+    /// Whether a slider handle is following the mouse
+    pub slider_dragging: bool,
+}
+
+impl Default for UiState {
+    fn default() -> UiState {
+        UiState::new()
+    }
+}
+
+impl UiState {
+    pub fn new() -> UiState {
+        UiState {
+            grid_offset: 0,
+            columns: 0,
+            help_scroll: 0,
+            themes: Theme::builtin(),
+            selected_theme: 0,
+            slider_dragging: false,
+        }
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.selected_theme]
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.selected_theme = (self.selected_theme + 1) % self.themes.len();
+    }
+
+    /// Adds a theme and switches to it, replacing any existing theme with the same name
+    pub fn add_theme(&mut self, theme: Theme) {
+        match self.themes.iter().position(|t| t.name == theme.name) {
+            Some(idx) => {
+                self.themes[idx] = theme;
+                self.selected_theme = idx;
+            }
+            None => {
+                self.themes.push(theme);
+                self.selected_theme = self.themes.len() - 1;
+            }
+        }
+    }
+
+    /// Switches to the theme with the given name. Returns false if there is no such theme.
+    pub fn select_theme(&mut self, name: &str) -> bool {
+        match self.themes.iter().position(|t| t.name == name) {
+            Some(idx) => {
+                self.selected_theme = idx;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// The menu keeps its own selection states, which are converted to ratatui's for drawing
+
+impl From<&TableState> for widgets::TableState {
+    fn from(state: &TableState) -> widgets::TableState {
+        let mut widget_state = widgets::TableState::default();
+        match state.selected() {
+            Some(TableSelection::Cell { row, col }) => {
+                widget_state.select(Some(widgets::TableSelection::Cell { row, col }))
+            }
+            Some(TableSelection::Row(row)) => widget_state.select_row(Some(row)),
+            None => {}
+        }
+        *widget_state.offset_mut() = state.offset();
+        widget_state
+    }
+}
+
+impl From<&ListState> for widgets::ListState {
+    fn from(state: &ListState) -> widgets::ListState {
+        let mut widget_state = widgets::ListState::default();
+        widget_state.select(state.selected());
+        *widget_state.offset_mut() = state.offset();
+        widget_state
+    }
+}
+
+/// Draws a table with a selection state, keeping the scroll offset ratatui chose
+/// so that it carries over to the next frame
+pub(crate) fn render_table(
+    frame: &mut ratatui::Frame,
+    table: widgets::Table,
+    area: ratatui::layout::Rect,
+    state: &mut TableState,
+) {
+    let mut widget_state = widgets::TableState::from(&*state);
+    frame.render_stateful_widget(table, area, &mut widget_state);
+    *state.offset_mut() = widget_state.offset();
+}
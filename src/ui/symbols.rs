@@ -0,0 +1,62 @@
+/// Glyphs used to draw toggles. Terminals with limited fonts can use the ASCII set instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SymbolSet {
+    pub name: &'static str,
+    /// Options in a ToggleSingle submenu, where exactly one can be chosen
+    pub radio_on: &'static str,
+    pub radio_off: &'static str,
+    /// Options in a ToggleMultiple submenu, which can be turned on and off independently
+    pub checkbox_on: &'static str,
+    pub checkbox_off: &'static str,
+    /// Levels of a toggle with more than two values, one pip per level
+    pub pip_on: &'static str,
+    pub pip_off: &'static str,
+}
+
+pub const UNICODE_SYMBOLS: SymbolSet = SymbolSet {
+    name: "unicode",
+    radio_on: "◉",
+    radio_off: "○",
+    checkbox_on: "☑",
+    checkbox_off: "☐",
+    pip_on: "●",
+    pip_off: "·",
+};
+
+pub const ASCII_SYMBOLS: SymbolSet = SymbolSet {
+    name: "ascii",
+    radio_on: "(*)",
+    radio_off: "( )",
+    checkbox_on: "[x]",
+    checkbox_off: "[ ]",
+    pip_on: "#",
+    pip_off: ".",
+};
+
+impl Default for SymbolSet {
+    fn default() -> SymbolSet {
+        UNICODE_SYMBOLS
+    }
+}
+
+impl SymbolSet {
+    /// Looks up a built-in symbol set by name, ignoring case
+    pub fn by_name(name: &str) -> Option<SymbolSet> {
+        [UNICODE_SYMBOLS, ASCII_SYMBOLS]
+            .into_iter()
+            .find(|symbols| symbols.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Pips showing `value` out of `max`, e.g. "●●··" for 2 out of 4
+    pub fn pips(&self, value: u8, max: u8) -> String {
+        (1..=max)
+            .map(|level| {
+                if level <= value {
+                    self.pip_on
+                } else {
+                    self.pip_off
+                }
+            })
+            .collect()
+    }
+}
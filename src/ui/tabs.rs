@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+/// Cells taken up by the padding on either side of each tab title
+const TAB_PADDING: usize = 2;
+/// Cells taken up by the divider between tabs
+const TAB_DIVIDER: usize = 1;
+
+/// The range of tabs which fit in a tab bar `width` cells wide, given the width of each title.
+///
+/// The selected tab is always included, even if it has to be clipped.
+/// Tabs are then added alternately after and before it for as long as they fit,
+/// so the selected tab stays near the middle while scrolling through a long list.
+pub fn visible_tabs(title_widths: &[usize], selected: usize, width: usize) -> Range<usize> {
+    if title_widths.is_empty() {
+        return 0..0;
+    }
+    let selected = selected.min(title_widths.len() - 1);
+    let mut range = selected..selected + 1;
+    let mut used = title_widths[selected] + TAB_PADDING;
+    let (mut fits_after, mut fits_before) = (true, true);
+    while fits_after || fits_before {
+        if fits_after {
+            fits_after = range.end < title_widths.len()
+                && try_add(&mut used, title_widths[range.end], width);
+            if fits_after {
+                range.end += 1;
+            }
+        }
+        if fits_before {
+            fits_before =
+                range.start > 0 && try_add(&mut used, title_widths[range.start - 1], width);
+            if fits_before {
+                range.start -= 1;
+            }
+        }
+    }
+    range
+}
+
+fn try_add(used: &mut usize, title_width: usize, width: usize) -> bool {
+    let needed = *used + TAB_DIVIDER + title_width + TAB_PADDING;
+    if needed <= width {
+        *used = needed;
+        true
+    } else {
+        false
+    }
+}
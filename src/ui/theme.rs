@@ -0,0 +1,346 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde_json::Value;
+use std::{fmt, fs, io, path::Path};
+
+use crate::SymbolSet;
+
+/// Every style used by the renderer.
+///
+/// Themes can be built in code, picked from the built-in themes, or loaded from a JSON file:
+///
+/// ```json
+/// {
+///     "name": "Midnight",
+///     "base": "High Contrast",
+///     "highlight": { "fg": "black", "bg": "#5fafff", "modifiers": ["bold"] },
+///     "slider_hover": { "fg": "magenta" }
+/// }
+/// ```
+///
+/// Styles missing from the file are taken from the `base` theme, or the default theme if
+/// no base is given. Colours are anything ratatui can parse: names, indices or `#rrggbb`.
+/// The toggle glyphs can be switched with `"symbols": "ascii"` or `"symbols": "unicode"`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// Glyphs for radio buttons, checkboxes and pips on toggle pages
+    pub symbols: SymbolSet,
+    /// Fills the whole frame before anything else is drawn
+    pub background: Style,
+    pub text: Style,
+    pub border: Style,
+    /// The selected cell of a submenu or toggle grid
+    pub highlight: Style,
+    /// The current value shown under each submenu title
+    pub summary: Style,
+    pub tab: Style,
+    pub tab_selected: Style,
+    pub help: Style,
+    /// The result of the last action, shown in place of the help text
+    pub message: Style,
+    pub error: Style,
+    pub entry_cursor: Style,
+    /// Button glyphs in help text, such as <A>
+    pub button: Style,
+    pub slider_track: Style,
+    pub slider_range: Style,
+    pub slider_hover: Style,
+    pub slider_selected: Style,
+    pub slider_label: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            name: "Default".to_string(),
+            symbols: SymbolSet::default(),
+            background: Style::default(),
+            text: Style::default(),
+            border: Style::default(),
+            highlight: Style::default().bg(Color::Gray),
+            summary: Style::default().fg(Color::DarkGray),
+            tab: Style::default(),
+            tab_selected: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            help: Style::default(),
+            message: Style::default(),
+            error: Style::default().fg(Color::Red),
+            entry_cursor: Style::default().add_modifier(Modifier::REVERSED),
+            button: Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            slider_track: Style::default().fg(Color::White),
+            slider_range: Style::default().fg(Color::Yellow),
+            slider_hover: Style::default().fg(Color::Red),
+            slider_selected: Style::default().fg(Color::Green),
+            slider_label: Style::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// Bright colours on black, with bold highlights
+    pub fn high_contrast() -> Theme {
+        let text = Style::default().fg(Color::White).bg(Color::Black);
+        Theme {
+            name: "High Contrast".to_string(),
+            symbols: SymbolSet::default(),
+            background: text,
+            text,
+            border: Style::default().fg(Color::White),
+            highlight: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            summary: Style::default().fg(Color::LightCyan),
+            tab: text,
+            tab_selected: Style::default()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            help: text,
+            message: Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            error: Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            entry_cursor: Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            button: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            slider_track: Style::default().fg(Color::Gray),
+            slider_range: Style::default().fg(Color::LightCyan),
+            slider_hover: Style::default()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            slider_selected: Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+            slider_label: text,
+        }
+    }
+
+    /// Uses the Okabe-Ito palette, which avoids relying on red/green differences
+    pub fn colour_blind_safe() -> Theme {
+        let orange = Color::Rgb(230, 159, 0);
+        let sky_blue = Color::Rgb(86, 180, 233);
+        let yellow = Color::Rgb(240, 228, 66);
+        let blue = Color::Rgb(0, 114, 178);
+        let vermillion = Color::Rgb(213, 94, 0);
+        Theme {
+            name: "Colour-Blind Safe".to_string(),
+            highlight: Style::default().fg(Color::Black).bg(sky_blue),
+            tab_selected: Style::default().fg(orange).add_modifier(Modifier::BOLD),
+            error: Style::default().fg(vermillion),
+            slider_track: Style::default().fg(Color::Gray),
+            slider_range: Style::default().fg(yellow),
+            slider_hover: Style::default().fg(orange),
+            slider_selected: Style::default().fg(blue),
+            ..Theme::default()
+        }
+    }
+
+    /// Uses modifiers only, for terminals without colour support
+    pub fn monochrome() -> Theme {
+        Theme {
+            name: "Monochrome".to_string(),
+            symbols: SymbolSet::default(),
+            background: Style::default(),
+            text: Style::default(),
+            border: Style::default(),
+            highlight: Style::default().add_modifier(Modifier::REVERSED),
+            summary: Style::default().add_modifier(Modifier::DIM),
+            tab: Style::default(),
+            tab_selected: Style::default().add_modifier(Modifier::REVERSED),
+            help: Style::default(),
+            message: Style::default().add_modifier(Modifier::BOLD),
+            error: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            entry_cursor: Style::default().add_modifier(Modifier::REVERSED),
+            button: Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            slider_track: Style::default().add_modifier(Modifier::DIM),
+            slider_range: Style::default(),
+            slider_hover: Style::default().add_modifier(Modifier::BOLD),
+            slider_selected: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            slider_label: Style::default(),
+        }
+    }
+
+    /// All of the built-in themes, starting with the default
+    pub fn builtin() -> Vec<Theme> {
+        vec![
+            Theme::default(),
+            Theme::high_contrast(),
+            Theme::colour_blind_safe(),
+            Theme::monochrome(),
+        ]
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Theme, ThemeError> {
+        Theme::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Theme, ThemeError> {
+        let value: Value = serde_json::from_str(json)?;
+        let fields = value.as_object().ok_or(ThemeError::NotAnObject)?;
+        let mut theme = match fields.get("base") {
+            Some(Value::String(base)) => Theme::builtin()
+                .into_iter()
+                .find(|theme| theme.name.eq_ignore_ascii_case(base))
+                .ok_or_else(|| ThemeError::UnknownBase(base.clone()))?,
+            Some(_) => return Err(ThemeError::NotAString("base".to_string())),
+            None => Theme::default(),
+        };
+        for (key, value) in fields.iter() {
+            match key.as_str() {
+                "base" => {}
+                "name" => {
+                    theme.name = value
+                        .as_str()
+                        .ok_or_else(|| ThemeError::NotAString(key.clone()))?
+                        .to_string()
+                }
+                "symbols" => {
+                    let name = value
+                        .as_str()
+                        .ok_or_else(|| ThemeError::NotAString(key.clone()))?;
+                    theme.symbols = SymbolSet::by_name(name)
+                        .ok_or_else(|| ThemeError::UnknownSymbols(name.to_string()))?;
+                }
+                _ => {
+                    let style = parse_style(key, value)?;
+                    *theme
+                        .style_mut(key)
+                        .ok_or_else(|| ThemeError::UnknownStyle(key.clone()))? = style;
+                }
+            }
+        }
+        Ok(theme)
+    }
+
+    fn style_mut(&mut self, key: &str) -> Option<&mut Style> {
+        let style = match key {
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "border" => &mut self.border,
+            "highlight" => &mut self.highlight,
+            "summary" => &mut self.summary,
+            "tab" => &mut self.tab,
+            "tab_selected" => &mut self.tab_selected,
+            "help" => &mut self.help,
+            "message" => &mut self.message,
+            "error" => &mut self.error,
+            "entry_cursor" => &mut self.entry_cursor,
+            "button" => &mut self.button,
+            "slider_track" => &mut self.slider_track,
+            "slider_range" => &mut self.slider_range,
+            "slider_hover" => &mut self.slider_hover,
+            "slider_selected" => &mut self.slider_selected,
+            "slider_label" => &mut self.slider_label,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+fn parse_style(key: &str, value: &Value) -> Result<Style, ThemeError> {
+    let fields = value.as_object().ok_or(ThemeError::NotAnObject)?;
+    let mut style = Style::default();
+    if let Some(fg) = fields.get("fg") {
+        style = style.fg(parse_color(key, fg)?);
+    }
+    if let Some(bg) = fields.get("bg") {
+        style = style.bg(parse_color(key, bg)?);
+    }
+    if let Some(modifiers) = fields.get("modifiers") {
+        let modifiers = modifiers
+            .as_array()
+            .ok_or_else(|| ThemeError::NotAnArray(format!("{}.modifiers", key)))?;
+        for modifier in modifiers.iter() {
+            let name = modifier
+                .as_str()
+                .ok_or_else(|| ThemeError::NotAString(format!("{}.modifiers", key)))?;
+            style = style.add_modifier(parse_modifier(name)?);
+        }
+    }
+    Ok(style)
+}
+
+fn parse_color(key: &str, value: &Value) -> Result<Color, ThemeError> {
+    let name = value
+        .as_str()
+        .ok_or_else(|| ThemeError::NotAString(key.to_string()))?;
+    name.parse::<Color>()
+        .map_err(|_| ThemeError::InvalidColor(name.to_string()))
+}
+
+fn parse_modifier(name: &str) -> Result<Modifier, ThemeError> {
+    let modifier = match name.to_ascii_lowercase().as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" => Modifier::UNDERLINED,
+        "slow_blink" => Modifier::SLOW_BLINK,
+        "rapid_blink" => Modifier::RAPID_BLINK,
+        "reversed" => Modifier::REVERSED,
+        "hidden" => Modifier::HIDDEN,
+        "crossed_out" => Modifier::CROSSED_OUT,
+        _ => return Err(ThemeError::UnknownModifier(name.to_string())),
+    };
+    Ok(modifier)
+}
+
+/// Why a theme file couldn't be loaded
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The theme, or one of its styles, isn't a JSON object
+    NotAnObject,
+    /// A name, colour or modifier isn't a JSON string
+    NotAString(String),
+    /// A list of modifiers isn't a JSON array
+    NotAnArray(String),
+    UnknownBase(String),
+    UnknownStyle(String),
+    InvalidColor(String),
+    UnknownModifier(String),
+    UnknownSymbols(String),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThemeError::Io(err) => write!(f, "Could not read the theme file: {}", err),
+            ThemeError::Json(err) => write!(f, "Could not parse the theme file: {}", err),
+            ThemeError::NotAnObject => write!(f, "Themes and styles must be JSON objects"),
+            ThemeError::NotAString(key) => write!(f, "Expected a string for {}", key),
+            ThemeError::NotAnArray(key) => write!(f, "Expected an array for {}", key),
+            ThemeError::UnknownBase(name) => write!(f, "No built-in theme named {}", name),
+            ThemeError::UnknownStyle(key) => write!(f, "Unknown style {}", key),
+            ThemeError::InvalidColor(name) => write!(f, "Invalid colour {}", name),
+            ThemeError::UnknownModifier(name) => write!(f, "Unknown modifier {}", name),
+            ThemeError::UnknownSymbols(name) => write!(f, "No symbol set named {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl From<io::Error> for ThemeError {
+    fn from(err: io::Error) -> ThemeError {
+        ThemeError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ThemeError {
+    fn from(err: serde_json::Error) -> ThemeError {
+        ThemeError::Json(err)
+    }
+}
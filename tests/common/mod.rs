@@ -0,0 +1,139 @@
+//! Menus shared by the app and snapshot tests
+#![allow(dead_code)]
+
+use training_mod_tui_2::*;
+
+pub fn make_toggle_submenu<'a>(title: &'a str, id: &'a str) -> SubMenu<'a> {
+    let toggles = vec![
+        Toggle {
+            title: "On",
+            value: 1,
+            max: 1,
+            help_text: "",
+        },
+        Toggle {
+            title: "Off",
+            value: 0,
+            max: 1,
+            help_text: "",
+        },
+    ];
+    SubMenu {
+        title,
+        id,
+        help_text: "A Toggle",
+        submenu_type: SubMenuType::ToggleSingle,
+        toggles: StatefulTable::with_items(2, 2, toggles),
+        slider: None,
+        submenus: None,
+    }
+}
+
+pub fn make_multi_toggle_submenu<'a>(title: &'a str, id: &'a str) -> SubMenu<'a> {
+    let toggles = ["Off", "Low", "Medium", "High"]
+        .iter()
+        .enumerate()
+        .map(|(i, title)| Toggle {
+            title,
+            value: if i == 0 { 1 } else { 0 },
+            max: 1,
+            help_text: "",
+        })
+        .collect();
+    SubMenu {
+        title,
+        id,
+        help_text: "Several Toggles\n- <A> turns one on or off",
+        submenu_type: SubMenuType::ToggleMultiple,
+        toggles: StatefulTable::with_items(2, 3, toggles),
+        slider: None,
+        submenus: None,
+    }
+}
+
+pub fn make_action_submenu<'a>(title: &'a str, id: &'a str) -> SubMenu<'a> {
+    SubMenu {
+        title,
+        id,
+        help_text: "An Action",
+        submenu_type: SubMenuType::Action,
+        toggles: StatefulTable::new(0, 0),
+        slider: None,
+        submenus: None,
+    }
+}
+
+pub fn make_nested_submenu<'a>(
+    title: &'a str,
+    id: &'a str,
+    items: Vec<SubMenu<'a>>,
+) -> SubMenu<'a> {
+    SubMenu {
+        title,
+        id,
+        help_text: "A Nested Menu",
+        submenu_type: SubMenuType::Nested,
+        toggles: StatefulTable::new(0, 0),
+        slider: None,
+        submenus: Some(StatefulTable::with_items(2, 2, items)),
+    }
+}
+
+pub fn make_slider_submenu<'a>(title: &'a str, id: &'a str) -> SubMenu<'a> {
+    SubMenu {
+        title,
+        id,
+        help_text: "A Slider",
+        submenu_type: SubMenuType::Slider,
+        toggles: StatefulTable::new(0, 0),
+        slider: Some(StatefulSlider::new().into()),
+        submenus: None,
+    }
+}
+
+pub fn make_tab<'a>(title: &'a str, id: &'a str, submenus: Vec<SubMenu<'a>>) -> Tab<'a> {
+    Tab {
+        title,
+        id,
+        submenus: StatefulTable::with_items(2, 2, submenus),
+    }
+}
+
+pub fn initialize_app<'a>() -> App<'a> {
+    let mut app = App::new();
+    let submenus = vec![
+        make_toggle_submenu("Toggle", "toggle"),
+        make_action_submenu("Host Action", "host_action"),
+        make_action_submenu("Reset All", ACTION_RESET_DEFAULTS),
+    ];
+    app.tabs = StatefulList::with_items(vec![make_tab("Tab", "tab", submenus)]);
+    app
+}
+
+/// One tab of each kind of submenu
+fn make_full_tab<'a>(title: &'a str, id: &'a str) -> Tab<'a> {
+    make_tab(
+        title,
+        id,
+        vec![
+            make_toggle_submenu("Single Toggle", "single"),
+            make_multi_toggle_submenu("Multi Toggle", "multiple"),
+            make_slider_submenu("Damage Range", "damage"),
+            make_action_submenu("Reset All", ACTION_RESET_DEFAULTS),
+        ],
+    )
+}
+
+/// Enough tabs that the tab bar has to scroll in an 80 column terminal
+pub fn initialize_tabbed_app<'a>() -> App<'a> {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![
+        make_full_tab("Mash Settings", "mash"),
+        make_full_tab("Defensive Settings", "defensive"),
+        make_full_tab("Save States", "save_states"),
+        make_full_tab("Miscellaneous", "misc"),
+        make_full_tab("Button Config", "button"),
+        make_full_tab("Input Recording", "recording"),
+    ]);
+    app
+}
@@ -0,0 +1,798 @@
+#[cfg(feature = "has_terminal")]
+use ratatui::layout::Rect;
+use serde_json::json;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use training_mod_tui_2::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn app_serialize_skips_actions() {
+    let app = initialize_app();
+    assert_eq!(app.to_json(), "{\"Toggle\":[1,0]}");
+}
+
+#[test]
+fn app_action_runs_callback() {
+    let mut app = initialize_app();
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    app.register_action(
+        "host_action",
+        None,
+        Box::new(move |_: &mut App| {
+            counter.set(counter.get() + 1);
+            Some("Done".to_string())
+        }),
+    );
+    app.selected_tab().submenus.select(0, 1);
+    app.on_a();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(count.get(), 1);
+    assert_eq!(app.action_message, Some("Done".to_string()));
+
+    // Moving the selection clears the message
+    app.on_left();
+    assert_eq!(app.action_message, None);
+}
+
+#[test]
+fn app_action_confirmation() {
+    let mut app = initialize_app();
+    app.save_default_settings();
+    app.selected_tab().submenus.select(0, 0);
+    app.on_a();
+    app.selected_submenu().toggles.select(0, 1);
+    app.on_a();
+    app.on_b();
+    assert_eq!(app.to_json(), "{\"Toggle\":[0,1]}");
+
+    // Cancelling the confirmation leaves the settings untouched
+    app.selected_tab().submenus.select(1, 0);
+    app.on_a();
+    assert!(app.page == AppPage::CONFIRMATION);
+    assert_eq!(
+        app.pending_confirmation(),
+        Some("Reset all settings to their defaults?")
+    );
+    app.on_b();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(app.pending_action, None);
+    assert_eq!(app.to_json(), "{\"Toggle\":[0,1]}");
+
+    // Confirming runs the action
+    app.on_a();
+    assert!(app.page == AppPage::CONFIRMATION);
+    app.on_a();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(app.to_json(), "{\"Toggle\":[1,0]}");
+}
+
+#[test]
+fn app_action_confirmation_returns_to_page() {
+    let mut app = initialize_app();
+    app.selected_tab().submenus.select(0, 0);
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+
+    // Cancelling a reset started from the toggles goes back to them
+    app.on_r();
+    assert!(app.page == AppPage::CONFIRMATION);
+    app.on_b();
+    assert!(app.page == AppPage::TOGGLE);
+
+    // So does confirming it
+    app.on_r();
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+}
+
+#[test]
+fn app_action_unregistered() {
+    let mut app = initialize_app();
+    app.selected_tab().submenus.select(0, 1);
+    app.on_a();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(
+        app.action_message,
+        Some("No action registered for host_action".to_string())
+    );
+}
+
+fn initialize_nested_app<'a>() -> App<'a> {
+    let mut app = App::new();
+    let inner = make_nested_submenu(
+        "Inner",
+        "inner",
+        vec![make_toggle_submenu("Deep Toggle", "deep_toggle")],
+    );
+    let outer = make_nested_submenu(
+        "Outer",
+        "outer",
+        vec![make_toggle_submenu("Nested Toggle", "nested_toggle"), inner],
+    );
+    app.tabs = StatefulList::with_items(vec![make_tab(
+        "Tab",
+        "tab",
+        vec![make_toggle_submenu("Toggle", "toggle"), outer],
+    )]);
+    app
+}
+
+#[test]
+fn app_nested_navigation() {
+    let mut app = initialize_nested_app();
+    app.on_right();
+    assert_eq!(app.breadcrumbs(), vec!["Tab"]);
+
+    // Open the outer submenu
+    app.on_a();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(app.submenu_depth, 1);
+    assert_eq!(app.breadcrumbs(), vec!["Tab", "Outer"]);
+    assert_eq!(app.selected_submenu().title, "Nested Toggle");
+
+    // Open the inner submenu
+    app.on_right();
+    app.on_a();
+    assert_eq!(app.submenu_depth, 2);
+    assert_eq!(app.breadcrumbs(), vec!["Tab", "Outer", "Inner"]);
+    assert_eq!(app.selected_submenu().title, "Deep Toggle");
+
+    // Toggle pages return to the nested grid they were opened from
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+    app.on_b();
+    assert!(app.page == AppPage::SUBMENU);
+    assert_eq!(app.submenu_depth, 2);
+
+    // B walks back up one level at a time
+    app.on_b();
+    assert_eq!(app.submenu_depth, 1);
+    assert_eq!(app.selected_submenu().title, "Inner");
+    app.on_b();
+    assert_eq!(app.submenu_depth, 0);
+    assert_eq!(app.selected_submenu().title, "Outer");
+    app.on_b();
+    assert!(app.page == AppPage::CLOSE);
+}
+
+#[test]
+fn app_update_from_invalid_json() {
+    let mut app = initialize_nested_app();
+    let json = app.to_json();
+    assert!(matches!(
+        app.update_from_json("{"),
+        Err(SettingsError::Json(_))
+    ));
+    // Every value is checked before any of them are loaded
+    let invalid = [
+        "{\"Toggle\":[0,1],\"Outer\":[1,0]}",
+        "{\"Toggle\":[0,1],\"Outer\":{\"nested_toggle\":\"on\"}}",
+        "{\"Toggle\":[0,1],\"Outer\":{\"inner\":{\"deep_toggle\":[2,0]}}}",
+    ];
+    for invalid in invalid {
+        assert!(matches!(
+            app.update_from_json(invalid),
+            Err(SettingsError::InvalidValue(_))
+        ));
+    }
+    assert_eq!(app.to_json(), json);
+    assert!(app.drain_events().is_empty());
+}
+
+#[test]
+fn app_nested_serialize() {
+    let mut app = initialize_nested_app();
+    // Nested settings are keyed by id, so they survive their titles changing
+    let json =
+        "{\"Toggle\":[1,0],\"Outer\":{\"nested_toggle\":[1,0],\"inner\":{\"deep_toggle\":[1,0]}}}";
+    assert_eq!(app.to_json(), json);
+
+    let updated = "{\"Outer\":{\"inner\":{\"deep_toggle\":[0,1]}}}";
+    app.update_from_json(updated).unwrap();
+    let json =
+        "{\"Toggle\":[1,0],\"Outer\":{\"nested_toggle\":[1,0],\"inner\":{\"deep_toggle\":[0,1]}}}";
+    assert_eq!(app.to_json(), json);
+}
+
+#[test]
+fn app_help_overlay() {
+    let mut app = initialize_app();
+    app.on_y();
+    assert!(app.help_open);
+    assert_eq!(app.focused_help(), Some(("Toggle", "A Toggle")));
+
+    // Navigation scrolls the help instead of moving the selection
+    app.on_down();
+    app.on_down();
+    app.on_up();
+    assert_eq!(app.help_scroll, 1);
+    app.on_right();
+    assert_eq!(app.selected_tab().submenus.state.selected_col(), Some(0));
+    app.on_a();
+    assert!(app.page == AppPage::SUBMENU);
+
+    // B closes the help before it closes the menu
+    app.on_b();
+    assert!(!app.help_open);
+    assert_eq!(app.help_scroll, 0);
+    assert!(app.page == AppPage::SUBMENU);
+
+    // Toggles show their own help if they have any
+    app.on_a();
+    app.selected_submenu()
+        .toggles
+        .get_by_idx_mut(1)
+        .unwrap()
+        .help_text = "Turn it off";
+    app.selected_submenu().toggles.select(0, 1);
+    assert_eq!(app.focused_help(), Some(("Off", "Turn it off")));
+    app.selected_submenu().toggles.select(0, 0);
+    assert_eq!(app.focused_help(), Some(("Toggle", "A Toggle")));
+    app.on_y();
+    assert!(app.help_open);
+    app.on_y();
+    assert!(!app.help_open);
+}
+
+#[test]
+fn app_button_hints() {
+    let mut app = initialize_app();
+    let hints = app.button_hints();
+    assert_eq!(
+        hints[0],
+        ButtonHint {
+            button: Button::A,
+            action: "Open"
+        }
+    );
+    assert_eq!(
+        hints[1],
+        ButtonHint {
+            button: Button::B,
+            action: "Exit"
+        }
+    );
+    // With only one tab, there's nothing to switch to
+    assert!(!hints.iter().any(|hint| hint.button == Button::ZR));
+
+    app.selected_tab().submenus.select(0, 1);
+    assert_eq!(app.button_hints()[0].action, "Run");
+
+    app.selected_tab().submenus.select(1, 0);
+    app.on_a();
+    assert!(app.page == AppPage::CONFIRMATION);
+    let buttons: Vec<Button> = app.button_hints().iter().map(|hint| hint.button).collect();
+    assert_eq!(buttons, vec![Button::A, Button::B]);
+    app.on_b();
+
+    app.on_y();
+    assert_eq!(app.button_hints()[0].action, "Close help");
+}
+
+#[test]
+fn app_button_label() {
+    let mut app = initialize_app();
+    assert_eq!(app.button_label(Button::ZL), "ZL");
+    app.input_source = InputSource::Keyboard(std::collections::BTreeMap::from([(
+        Button::ZL,
+        "o".to_string(),
+    )]));
+    assert_eq!(app.button_label(Button::ZL), "o");
+    // Buttons without a key fall back to the controller glyph
+    assert_eq!(app.button_label(Button::Up), "↑");
+}
+
+/// A copy of the open u32 slider
+fn open_slider(app: &mut App) -> StatefulSlider {
+    app.selected_submenu()
+        .slider
+        .as_ref()
+        .unwrap()
+        .get()
+        .unwrap()
+        .clone()
+}
+
+#[test]
+#[cfg(feature = "has_terminal")]
+fn app_mouse_clicks() {
+    let mut app = initialize_app();
+    app.tabs.items.push(Tab {
+        title: "Sliders",
+        id: "sliders",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    });
+    let layout = UiLayout {
+        tabs: vec![(0, Rect::new(0, 0, 5, 1)), (1, Rect::new(6, 0, 9, 1))],
+        cells: vec![
+            (0, 0, Rect::new(0, 2, 10, 2)),
+            (0, 1, Rect::new(11, 2, 10, 2)),
+        ],
+        slider: None,
+    };
+
+    let mut ui_state = UiState::default();
+
+    // The first click selects a submenu and the second opens it
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 12, y: 3 });
+    assert_eq!(app.selected_submenu().id, "host_action");
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 1, y: 2 });
+    assert_eq!(app.selected_submenu().id, "toggle");
+    assert!(app.page == AppPage::SUBMENU);
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 1, y: 2 });
+    assert!(app.page == AppPage::TOGGLE);
+
+    // Clicking a toggle selects and toggles it
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 12, y: 2 });
+    let toggles = &app.selected_submenu().toggles;
+    assert_eq!(toggles.get(0, 0).unwrap().value, 0);
+    assert_eq!(toggles.get(0, 1).unwrap().value, 1);
+
+    // Tabs can only be clicked from the list of submenus
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 7, y: 0 });
+    assert_eq!(app.selected_tab().id, "tab");
+    app.on_b();
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 7, y: 0 });
+    assert_eq!(app.selected_tab().id, "sliders");
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::ScrollUp);
+    assert_eq!(app.selected_tab().id, "tab");
+}
+
+#[test]
+#[cfg(feature = "has_terminal")]
+fn app_mouse_slider() {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    }]);
+    app.on_a();
+    assert!(app.page == AppPage::SLIDER);
+    let layout = UiLayout {
+        tabs: vec![],
+        cells: vec![],
+        slider: Some(Rect::new(0, 5, 151, 1)),
+    };
+
+    let mut ui_state = UiState::default();
+
+    // Pressing grabs the nearest handle and dragging moves it
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 120, y: 5 });
+    assert!(ui_state.slider_dragging);
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Drag { x: 100, y: 7 });
+    let slider = open_slider(&mut app);
+    assert_eq!((slider.lower, slider.upper), (0, 100));
+    assert_eq!(slider.state, SliderState::UpperSelected);
+
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Release);
+    assert!(!ui_state.slider_dragging);
+    assert_eq!(open_slider(&mut app).state, SliderState::UpperSelected);
+
+    // Scrolling moves the handle that was dragged
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::ScrollDown);
+    assert_eq!(open_slider(&mut app).upper, 99);
+
+    // Dragging follows the slider's mode
+    app.on_b();
+    app.on_l();
+    app.on_l();
+    assert_eq!(open_slider(&mut app).mode, SliderMode::LockedWidth);
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Press { x: 0, y: 5 });
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Drag { x: 20, y: 5 });
+    app.handle_mouse(&layout, &mut ui_state, MouseInput::Release);
+    let slider = open_slider(&mut app);
+    assert_eq!((slider.lower, slider.upper), (20, 119));
+    assert_eq!(slider.state, SliderState::LowerSelected);
+}
+
+#[test]
+fn app_view() {
+    let mut app = initialize_app();
+    let view = app.view();
+    assert_eq!(view.breadcrumbs, vec!["Tab".to_string()]);
+    assert_eq!(view.selected_tab, 0);
+    assert_eq!(view.footer, FooterView::Help("A Toggle".to_string()));
+    assert_eq!(view.help, None);
+    match &view.page {
+        PageView::Submenus(grid) => {
+            assert_eq!((grid.rows, grid.cols), (2, 2));
+            assert_eq!(grid.selected, Some((0, 0)));
+            let titles: Vec<&str> = grid.cells.iter().map(|c| c.title.as_str()).collect();
+            assert_eq!(titles, vec!["Toggle", "Host Action", "Reset All"]);
+            assert_eq!((grid.cells[2].row, grid.cells[2].col), (1, 0));
+            assert_eq!(grid.cells[0].summary, "On");
+        }
+        _ => panic!("Expected the submenu page"),
+    }
+    assert_eq!(
+        view.hints[0],
+        HintView {
+            label: "A".to_string(),
+            action: "Open".to_string()
+        }
+    );
+
+    app.on_a();
+    match app.view().page {
+        PageView::Toggles { submenu_type, grid } => {
+            assert_eq!(submenu_type, SubMenuType::ToggleSingle);
+            assert_eq!(grid.cells[0].value, 1);
+            assert_eq!(grid.cells[1].title, "Off");
+        }
+        _ => panic!("Expected the toggle page"),
+    }
+
+    // The view can be handed to other frontends as JSON
+    let json = serde_json::to_value(app.view()).unwrap();
+    assert_eq!(json["tabs"][0]["title"], "Tab");
+    assert_eq!(json["page"]["Toggles"]["grid"]["cols"], 2);
+}
+
+#[test]
+#[cfg(feature = "has_terminal")]
+fn app_render_leaves_menu_alone() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut app = App::new();
+    let submenus = vec![
+        make_toggle_submenu("A Long Toggle Title", "first"),
+        make_toggle_submenu("Another Long Title", "second"),
+        make_toggle_submenu("Yet Another Title", "third"),
+    ];
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 3, submenus),
+    }]);
+    app.selected_tab().submenus.select(0, 2);
+
+    // Only one column fits, but that's up to the renderer
+    let mut terminal = Terminal::new(TestBackend::new(40, 24)).unwrap();
+    let mut ui_state = UiState::default();
+    let mut layout = UiLayout::default();
+    terminal
+        .draw(|f| layout = render_ui(f, &app, &mut ui_state))
+        .unwrap();
+    assert_eq!(ui_state.columns, 1);
+    let table = app.current_submenu_table();
+    assert_eq!((table.rows, table.cols), (1, 3));
+    assert_eq!(table.state.selected_col(), Some(2));
+
+    // Reflowing the grid to fit keeps the same submenu selected
+    assert!(app.fit_columns(ui_state.columns));
+    assert!(!app.fit_columns(ui_state.columns));
+    let table = app.current_submenu_table();
+    assert_eq!((table.rows, table.cols), (3, 1));
+    assert_eq!(app.current_submenu().id, "third");
+    terminal
+        .draw(|f| layout = render_ui(f, &app, &mut ui_state))
+        .unwrap();
+    let (row, col, area) = layout.cells[2];
+    assert_eq!((row, col), (2, 0));
+    assert_eq!(
+        layout.hit_test(area.x, area.y),
+        Some(HitTarget::Cell { row: 2, col: 0 })
+    );
+
+    // Moving the selection follows the grid on screen
+    app.on_up();
+    assert_eq!(app.current_submenu().id, "second");
+}
+
+#[test]
+fn app_view_slider() {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    }]);
+    app.on_a();
+    app.selected_submenu()
+        .slider
+        .as_mut()
+        .unwrap()
+        .get_mut::<u32>()
+        .unwrap()
+        .lower = 30;
+    app.on_a();
+    app.on_x();
+    let slider = match app.view().page {
+        PageView::Slider(slider) => slider,
+        _ => panic!("Expected the slider page"),
+    };
+    assert_eq!(slider.title, "Slider");
+    assert_eq!(slider.state, SliderState::LowerSelected);
+    assert_eq!((slider.lower, slider.upper), (0.2, 1.0));
+    assert_eq!(slider.lower_label, "30");
+    assert_eq!(slider.max_label, "150");
+    let entry = slider.entry.unwrap();
+    let text: String = entry.glyphs.iter().map(|g| g.text.as_str()).collect();
+    assert_eq!(text, "030");
+    assert_eq!(entry.glyphs.iter().filter(|g| g.cursor).count(), 1);
+}
+
+#[test]
+fn app_session_round_trip() {
+    let mut app = initialize_nested_app();
+    app.selected_tab().submenus.select(0, 1);
+    app.on_a();
+    app.selected_submenu_table().select(0, 0);
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+    let json = app.session_to_json();
+
+    let mut restored = initialize_nested_app();
+    restored.restore_session_from_json(&json).unwrap();
+    assert_eq!(restored.submenu_depth, 1);
+    assert_eq!(restored.page, AppPage::TOGGLE);
+    assert_eq!(restored.breadcrumbs(), app.breadcrumbs());
+    assert_eq!(restored.selected_submenu().id, app.selected_submenu().id);
+
+    // A corrupt session is reported without moving the menu
+    let before = restored.session_state();
+    assert!(restored.restore_session_from_json("not json").is_err());
+    assert_eq!(restored.session_state(), before);
+}
+
+#[test]
+fn app_session_stale_positions() {
+    let mut app = initialize_app();
+    app.selected_tab().submenus.select(1, 0);
+    let mut session = app.session_state();
+
+    // The submenu is found by its id, even if it has moved
+    let mut moved = initialize_app();
+    moved.selected_tab().submenus.reflow(1);
+    moved.restore_session(&session);
+    assert_eq!(moved.selected_submenu().id, ACTION_RESET_DEFAULTS);
+
+    // Unknown ids fall back to the nearest position which exists
+    session.tabs[0].selection = CellSession {
+        row: 5,
+        col: 5,
+        id: "removed".to_string(),
+    };
+    session.selected_tab = 3;
+    session.open_submenus = vec![session.tabs[0].selection.clone()];
+    session.page = AppPage::SLIDER;
+    let mut restored = initialize_app();
+    restored.restore_session(&session);
+    assert_eq!(restored.selected_submenu().id, ACTION_RESET_DEFAULTS);
+    assert_eq!(restored.tabs.state.selected(), Some(0));
+    assert_eq!(restored.submenu_depth, 0);
+    // A page which doesn't match the selected submenu isn't reopened
+    assert_eq!(restored.page, AppPage::SUBMENU);
+}
+
+#[test]
+fn app_handle_input() {
+    let mut app = initialize_app();
+
+    // Presses act like the InputControl methods
+    app.handle_input(Button::A, ButtonEvent::Press);
+    assert_eq!(app.page, AppPage::TOGGLE);
+    assert!(app.is_held(Button::A));
+    assert_eq!(app.hold_count(Button::A), Some(0));
+
+    // Holding doesn't act again
+    app.handle_input(Button::A, ButtonEvent::Hold);
+    app.handle_input(Button::A, ButtonEvent::Hold);
+    assert_eq!(app.page, AppPage::TOGGLE);
+    assert_eq!(app.hold_count(Button::A), Some(2));
+    app.handle_input(Button::A, ButtonEvent::Release);
+    assert!(!app.is_held(Button::A));
+    assert_eq!(app.hold_count(Button::A), None);
+
+    // Several buttons can be down at once
+    app.handle_input(Button::ZL, ButtonEvent::Press);
+    app.handle_input(Button::B, ButtonEvent::Press);
+    assert_eq!(app.page, AppPage::SUBMENU);
+    assert!(app.is_held(Button::ZL) && app.is_held(Button::B));
+    app.handle_input(Button::B, ButtonEvent::Release);
+    assert!(app.is_held(Button::ZL) && !app.is_held(Button::B));
+
+    // A hold without a press is tracked, but doesn't act
+    app.handle_input(Button::B, ButtonEvent::Hold);
+    assert_eq!(app.page, AppPage::SUBMENU);
+    assert_eq!(app.hold_count(Button::B), Some(1));
+}
+
+#[test]
+fn app_held_direction_repeats() {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    }]);
+    app.auto_repeat = AutoRepeat {
+        initial_delay: 2,
+        interval: 1,
+        min_interval: 1,
+        accelerate_every: 0,
+        fast_after: Some(4),
+    };
+    app.on_a();
+    app.on_a();
+    let lower = |app: &mut App| open_slider(app).lower;
+
+    // The press steps once, then the held direction repeats slowly until it switches to fast
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    assert_eq!(lower(&mut app), 1);
+    let mut values = vec![];
+    for _ in 0..4 {
+        app.tick();
+        values.push(lower(&mut app));
+    }
+    assert_eq!(values, vec![1, 2, 3, 13]);
+
+    // Released buttons stop repeating
+    app.handle_input(Button::Right, ButtonEvent::Release);
+    app.tick();
+    assert_eq!(lower(&mut app), 13);
+
+    // Held directions repeat like presses where there's no slider handle to move
+    app.on_b();
+    assert_eq!(open_slider(&mut app).state, SliderState::LowerHover);
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    app.tick();
+    app.tick();
+    assert_eq!(open_slider(&mut app).state, SliderState::LowerHover);
+}
+
+#[test]
+fn app_held_direction_skips_tap_acceleration() {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    }]);
+    app.auto_repeat = AutoRepeat {
+        initial_delay: 1,
+        interval: 1,
+        min_interval: 1,
+        accelerate_every: 0,
+        fast_after: None,
+    };
+    app.on_a();
+    app.on_a();
+    // Every tap lands at the same moment, so only the streak length matters
+    app.selected_submenu()
+        .slider
+        .as_mut()
+        .unwrap()
+        .get_mut::<u32>()
+        .unwrap()
+        .accel = StepAcceleration::new(Duration::from_millis(300), 2).with_clock(|| Duration::ZERO);
+    let lower = |app: &mut App| open_slider(app).lower;
+
+    // Repeats of a held direction step slowly and don't count towards the tap streak
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    for _ in 0..3 {
+        app.tick();
+    }
+    app.handle_input(Button::Right, ButtonEvent::Release);
+    assert_eq!(lower(&mut app), 4);
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    app.handle_input(Button::Right, ButtonEvent::Release);
+    assert_eq!(lower(&mut app), 5);
+
+    // A third tap in the streak switches to the fast increment
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    assert_eq!(lower(&mut app), 15);
+}
+
+#[test]
+fn app_change_events() {
+    let mut app = initialize_app();
+    app.handle_input(Button::A, ButtonEvent::Press);
+    assert!(app.drain_events().is_empty());
+
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    assert_eq!(
+        app.drain_events(),
+        vec![MenuEvent::SettingChanged {
+            submenu_id: "toggle".to_string(),
+            old: json!([1, 0]),
+            new: json!([0, 1]),
+        }]
+    );
+
+    app.handle_input(Button::X, ButtonEvent::Press);
+    assert_eq!(app.drain_events(), vec![MenuEvent::DefaultsSaved]);
+
+    app.update_from_json("{\"Toggle\":[1,0]}").unwrap();
+    assert_eq!(
+        app.drain_events(),
+        vec![
+            MenuEvent::SettingChanged {
+                submenu_id: "toggle".to_string(),
+                old: json!([0, 1]),
+                new: json!([1, 0]),
+            },
+            MenuEvent::PresetLoaded,
+        ]
+    );
+
+    // Changes made through the InputControl methods are picked up when the events are drained
+    app.on_a();
+    assert_eq!(app.drain_events().len(), 1);
+
+    // Changes the host makes to the submenus itself are only compared once marked
+    app.selected_submenu().on_left();
+    app.selected_submenu().on_a();
+    assert!(app.drain_events().is_empty());
+    app.mark_settings_changed();
+    assert_eq!(app.drain_events().len(), 1);
+
+    app.handle_input(Button::Start, ButtonEvent::Press);
+    assert_eq!(app.drain_events(), vec![MenuEvent::MenuClosed]);
+    assert!(app.drain_events().is_empty());
+}
+
+#[test]
+fn app_change_events_shared_ids() {
+    // Every tab has submenus with the same ids
+    let mut app = initialize_tabbed_app();
+    app.handle_input(Button::ZR, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    assert_eq!(
+        app.drain_events(),
+        vec![MenuEvent::SettingChanged {
+            submenu_id: "single".to_string(),
+            old: json!([1, 0]),
+            new: json!([0, 1]),
+        }]
+    );
+
+    // The first tab's setting is compared against its own value
+    app.handle_input(Button::B, ButtonEvent::Press);
+    app.handle_input(Button::ZL, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    assert!(app.drain_events().is_empty());
+}
+
+#[test]
+fn app_change_event_subscriber() {
+    let mut app = initialize_app();
+    app.save_default_settings();
+    let received = Rc::new(RefCell::new(vec![]));
+    let receiver = received.clone();
+
+    // Events which were already queued are sent first
+    app.subscribe(Box::new(move |event: &MenuEvent| {
+        receiver.borrow_mut().push(event.clone())
+    }));
+    assert_eq!(*received.borrow(), vec![MenuEvent::DefaultsSaved]);
+
+    app.handle_input(Button::A, ButtonEvent::Press);
+    app.handle_input(Button::Right, ButtonEvent::Press);
+    app.handle_input(Button::A, ButtonEvent::Press);
+    app.handle_input(Button::B, ButtonEvent::Press);
+    app.handle_input(Button::B, ButtonEvent::Press);
+    assert_eq!(
+        *received.borrow(),
+        vec![
+            MenuEvent::DefaultsSaved,
+            MenuEvent::SettingChanged {
+                submenu_id: "toggle".to_string(),
+                old: json!([1, 0]),
+                new: json!([0, 1]),
+            },
+            MenuEvent::MenuClosed,
+        ]
+    );
+    assert!(app.drain_events().is_empty());
+}
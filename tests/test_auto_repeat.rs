@@ -0,0 +1,71 @@
+use std::time::Duration;
+use training_mod_tui_2::AutoRepeat;
+
+fn repeat_ticks(repeat: &AutoRepeat, until: u32) -> Vec<u32> {
+    (0..=until).filter(|t| repeat.repeats_at(*t)).collect()
+}
+
+#[test]
+fn auto_repeat_accelerates() {
+    let repeat = AutoRepeat {
+        initial_delay: 3,
+        interval: 4,
+        min_interval: 1,
+        accelerate_every: 2,
+        fast_after: Some(10),
+    };
+    // The interval shortens by a tick every two repeats, down to the minimum
+    assert_eq!(
+        repeat_ticks(&repeat, 25),
+        vec![3, 7, 11, 14, 17, 19, 21, 22, 23, 24, 25]
+    );
+    assert!(!repeat.is_fast(9));
+    assert!(repeat.is_fast(10));
+    assert!(repeat.is_fast(u32::MAX));
+}
+
+#[test]
+fn auto_repeat_without_acceleration() {
+    let repeat = AutoRepeat {
+        initial_delay: 5,
+        interval: 3,
+        min_interval: 1,
+        accelerate_every: 0,
+        fast_after: None,
+    };
+    assert_eq!(repeat_ticks(&repeat, 20), vec![5, 8, 11, 14, 17, 20]);
+    assert!(!repeat.is_fast(u32::MAX));
+
+    let disabled = AutoRepeat {
+        initial_delay: 0,
+        ..repeat
+    };
+    assert!(repeat_ticks(&disabled, 20).is_empty());
+}
+
+#[test]
+fn auto_repeat_for_tick_length() {
+    assert_eq!(
+        AutoRepeat::for_tick_length(Duration::from_millis(50)),
+        AutoRepeat {
+            initial_delay: 8,
+            interval: 2,
+            min_interval: 1,
+            accelerate_every: 4,
+            fast_after: Some(30),
+        }
+    );
+    assert_eq!(
+        AutoRepeat::default(),
+        AutoRepeat {
+            initial_delay: 24,
+            interval: 6,
+            min_interval: 2,
+            accelerate_every: 4,
+            fast_after: Some(90),
+        }
+    );
+    // Timings never round down to zero ticks
+    let slow = AutoRepeat::for_tick_length(Duration::from_secs(1));
+    assert_eq!((slow.interval, slow.min_interval), (1, 1));
+}
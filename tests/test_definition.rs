@@ -0,0 +1,212 @@
+use training_mod_tui_2::*;
+
+const DEFINITION: &str = r#"{"tabs": [
+    {"id": "mash", "title": "Mash Settings", "cols": 2, "submenus": [
+        {"id": "di", "title": "Set DI", "type": "ToggleSingle", "help_text": "Direction",
+         "toggles": [{"title": "Off", "value": 1}, {"title": "Away"}, {"title": "In"}]},
+        {"id": "dmg", "title": "Damage", "type": "Slider",
+         "slider": {"min": 0, "max": 150, "lower": 40, "incr_amount_fast": 5}},
+        {"id": "more", "title": "More", "type": "Nested", "submenus": [
+            {"id": "counts", "title": "Counts", "type": "ToggleMultiple",
+             "toggles": [{"title": "1", "max": 3}]}
+        ]},
+        {"id": "reset_defaults", "title": "Reset", "type": "Action"}
+    ]}
+]}"#;
+
+#[test]
+fn definition_build() {
+    let definition = MenuDefinition::from_json(DEFINITION).unwrap();
+    let mut app = definition.build();
+    let tab = app.selected_tab();
+    assert_eq!((tab.id, tab.title), ("mash", "Mash Settings"));
+    assert_eq!((tab.submenus.rows, tab.submenus.cols), (2, 2));
+
+    let di = app.selected_submenu();
+    assert_eq!(di.submenu_type, SubMenuType::ToggleSingle);
+    assert_eq!(di.help_text, "Direction");
+    // Toggle grids are as tall as they need to be
+    assert_eq!((di.toggles.rows, di.toggles.cols), (1, 4));
+    assert_eq!(di.toggles.get(0, 1).unwrap().max, 1);
+
+    app.selected_tab().submenus.select(0, 1);
+    let slider = app.selected_submenu().slider.clone().unwrap();
+    let slider = slider.get::<u32>().unwrap();
+    assert_eq!((slider.lower, slider.upper), (40, 150));
+    assert_eq!((slider.incr_amount_slow, slider.incr_amount_fast), (1, 5));
+
+    assert_eq!(
+        app.to_json(),
+        "{\"Set DI\":[1,0,0],\"Damage\":[40,150],\"More\":{\"counts\":[0]}}"
+    );
+}
+
+#[test]
+fn definition_slider_types() {
+    let definition = MenuDefinition::from_json(
+        r#"{"tabs": [{"id": "t", "title": "T", "submenus": [
+            {"id": "angle", "title": "Angle", "type": "Slider",
+             "slider": {"type": "f32", "min": -1.5, "max": 1.5, "incr_amount_slow": 0.5}},
+            {"id": "frames", "title": "Frames", "type": "Slider",
+             "slider": {"type": "u8", "min": 0, "max": 255, "upper": 60,
+                        "mode": "LockedWidth", "range_handle": true}}
+        ]}]}"#,
+    )
+    .unwrap();
+    let mut app = definition.build();
+    let angle = app.selected_submenu().slider.clone().unwrap();
+    assert_eq!(angle.slider_type(), SliderType::F32);
+    let angle = angle.get::<f32>().unwrap();
+    assert_eq!((angle.lower, angle.upper), (-1.5, 1.5));
+    assert_eq!(angle.incr_amount_slow, 0.5);
+
+    app.selected_tab().submenus.select(0, 1);
+    let frames = app.selected_submenu().slider.clone().unwrap();
+    assert_eq!(frames.slider_type(), SliderType::U8);
+    assert_eq!(frames.get::<u8>().unwrap().upper, 60);
+    assert_eq!(frames.mode(), SliderMode::LockedWidth);
+    assert!(frames.get::<u8>().unwrap().range_handle);
+    assert_eq!(frames.get::<u32>(), None);
+
+    // Each slider is saved and loaded in its own type
+    assert_eq!(app.to_json(), "{\"Angle\":[-1.5,1.5],\"Frames\":[0,60]}");
+    app.update_from_json("{\"Angle\":[-0.5,1.0],\"Frames\":[10,200]}")
+        .unwrap();
+    assert_eq!(app.to_json(), "{\"Angle\":[-0.5,1.0],\"Frames\":[10,200]}");
+}
+
+#[test]
+fn definition_slider_format() {
+    let definition = MenuDefinition::from_json(
+        r#"{"tabs": [{"id": "t", "title": "T", "submenus": [
+            {"id": "hitstun", "title": "Hitstun", "type": "Slider",
+             "slider": {"min": 0, "max": 120, "precision": 1, "presets": [0, 30, 60],
+                        "format": {"frames_per_second": 60, "unit": "s", "snap_to_ticks": true,
+                                   "ticks": [{"value": 60, "label": "1 second"}]}}}
+        ]}]}"#,
+    )
+    .unwrap();
+    let mut app = definition.build();
+    let slider = app.selected_submenu().slider.clone().unwrap();
+    let mut slider = slider.get::<u32>().unwrap().clone();
+    assert_eq!(slider.precision, Some(1));
+    assert_eq!(slider.format_value(90), "1.5s");
+    assert_eq!(slider.format.ticks[0].label, "1 second");
+    assert!(slider.format.snap_to_ticks);
+    assert_eq!(slider.presets, vec![0, 30, 60]);
+
+    slider.select_deselect();
+    slider.next_preset();
+    assert_eq!(slider.lower, 30);
+}
+
+#[test]
+fn definition_errors() {
+    let error = |json: &str| MenuDefinition::from_json(json).unwrap_err();
+    assert!(matches!(error("{"), DefinitionError::Json(_)));
+    assert!(matches!(error(r#"{"tabs": []}"#), DefinitionError::NoTabs));
+    assert!(matches!(
+        error(r#"{"tabs": [{"id": "t", "title": "T", "submenus": []}]}"#),
+        DefinitionError::Empty(id) if id == "t"
+    ));
+    let submenu_error = |submenu: &str| {
+        error(&format!(
+            r#"{{"tabs": [{{"id": "t", "title": "T", "submenus": [{}]}}]}}"#,
+            submenu
+        ))
+    };
+    assert!(matches!(
+        submenu_error(r#"{"id": "s", "title": "S", "type": "ToggleSingle"}"#),
+        DefinitionError::Empty(_)
+    ));
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "ToggleSingle", "toggles": [{"title": "A", "value": 2}]}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    assert!(matches!(
+        submenu_error(r#"{"id": "s", "title": "S", "type": "Slider"}"#),
+        DefinitionError::MissingSlider(_)
+    ));
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider", "slider": {"min": 10, "max": 5}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    // Numbers have to fit the slider's type
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider", "slider": {"type": "u8", "min": 0, "max": 300}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider", "slider": {"min": 0, "max": 1.5}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    // Presets and ticks have to be on the slider, and presets in order
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider", "slider": {"min": 0, "max": 5, "presets": [2, 6]}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider", "slider": {"min": 0, "max": 5, "presets": [3, 1]}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Slider",
+                "slider": {"min": 0, "max": 5, "format": {"ticks": [{"value": 9, "label": "X"}]}}}"#
+        ),
+        DefinitionError::InvalidValue(_)
+    ));
+    assert!(matches!(
+        submenu_error(r#"{"id": "s", "title": "S", "type": "Action", "cols": 0}"#),
+        DefinitionError::NoColumns(_)
+    ));
+    assert!(matches!(
+        submenu_error(r#"{"id": "s", "title": "S", "type": "None"}"#),
+        DefinitionError::InvalidType(_)
+    ));
+    // Problems inside Nested submenus are found too
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Nested", "submenus": [{"id": "x", "title": "X", "type": "Slider"}]}"#
+        ),
+        DefinitionError::MissingSlider(id) if id == "x"
+    ));
+    assert!(matches!(
+        submenu_error(r#"{"id": "s", "title": "S", "type": "Nested"}"#),
+        DefinitionError::Empty(id) if id == "s"
+    ));
+    // Ids can't be shared, even between a submenu and one nested further down
+    assert!(matches!(
+        submenu_error(
+            r#"{"id": "s", "title": "S", "type": "Nested", "submenus": [{"id": "s", "title": "X", "type": "Action"}]}"#
+        ),
+        DefinitionError::DuplicateId(id) if id == "s"
+    ));
+    assert!(matches!(
+        error(
+            r#"{"tabs": [{"id": "t", "title": "T", "submenus": [{"id": "a", "title": "A", "type": "Action"}]},
+                         {"id": "t", "title": "U", "submenus": [{"id": "b", "title": "B", "type": "Action"}]}]}"#
+        ),
+        DefinitionError::DuplicateId(id) if id == "t"
+    ));
+    // Saved settings are keyed by title, so submenus in tabs can't share one
+    assert!(matches!(
+        error(
+            r#"{"tabs": [{"id": "t", "title": "T", "submenus": [{"id": "a", "title": "A", "type": "Action"}]},
+                         {"id": "u", "title": "U", "submenus": [{"id": "b", "title": "A", "type": "Action"}]}]}"#
+        ),
+        DefinitionError::DuplicateTitle(title) if title == "A"
+    ));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "has_terminal")]
+
+use ratatui::layout::Rect;
+use training_mod_tui_2::*;
+
+fn initialize_layout() -> UiLayout {
+    UiLayout {
+        tabs: vec![(0, Rect::new(0, 0, 6, 1)), (1, Rect::new(7, 0, 8, 1))],
+        cells: vec![
+            (0, 0, Rect::new(0, 2, 10, 2)),
+            (0, 1, Rect::new(11, 2, 10, 2)),
+        ],
+        slider: Some(Rect::new(2, 10, 11, 1)),
+    }
+}
+
+#[test]
+fn hit_test_targets() {
+    let layout = initialize_layout();
+    assert_eq!(layout.hit_test(0, 0), Some(HitTarget::Tab(0)));
+    assert_eq!(layout.hit_test(14, 0), Some(HitTarget::Tab(1)));
+    // The divider between tabs
+    assert_eq!(layout.hit_test(6, 0), None);
+    assert_eq!(
+        layout.hit_test(12, 3),
+        Some(HitTarget::Cell { row: 0, col: 1 })
+    );
+    assert_eq!(layout.hit_test(10, 2), None);
+    assert_eq!(layout.hit_test(7, 10), Some(HitTarget::Slider(0.5)));
+    assert_eq!(layout.hit_test(7, 11), None);
+}
+
+#[test]
+fn hit_test_slider_fraction() {
+    let layout = initialize_layout();
+    assert_eq!(layout.slider_fraction(2), 0.0);
+    assert_eq!(layout.slider_fraction(12), 1.0);
+    // Dragging outside the slider clamps to its ends
+    assert_eq!(layout.slider_fraction(0), 0.0);
+    assert_eq!(layout.slider_fraction(40), 1.0);
+    assert_eq!(UiLayout::default().slider_fraction(5), 0.0);
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+use training_mod_tui_2::*;
+
+mod common;
+use common::*;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+fn filter() -> KeyRepeatFilter {
+    KeyRepeatFilter::new(ms(600), ms(100))
+}
+
+#[test]
+fn key_repeat_separate_taps() {
+    let mut keys = filter();
+    assert_eq!(keys.press(Button::Right, ms(0)), [ButtonEvent::Press]);
+    // A second tap before the first would have timed out still acts, as a new press
+    assert_eq!(
+        keys.press(Button::Right, ms(200)),
+        [ButtonEvent::Release, ButtonEvent::Press]
+    );
+    assert!(keys.release_expired(ms(700)).is_empty());
+    assert_eq!(keys.release_expired(ms(800)), vec![Button::Right]);
+    assert!(!keys.is_held(Button::Right));
+}
+
+#[test]
+fn key_repeat_held_key() {
+    let mut keys = filter();
+    assert_eq!(keys.press(Button::Left, ms(0)), [ButtonEvent::Press]);
+    // The terminal's first repeat comes after its delay, so it can't be told from a tap
+    assert_eq!(
+        keys.press(Button::Left, ms(500)),
+        [ButtonEvent::Release, ButtonEvent::Press]
+    );
+    // Presses at the terminal's repeat interval keep the key held
+    for t in [530, 560, 590, 620] {
+        assert!(keys.press(Button::Left, ms(t)).is_empty());
+    }
+    assert!(keys.release_expired(ms(700)).is_empty());
+    assert!(keys.is_held(Button::Left));
+    assert_eq!(keys.release_expired(ms(720)), vec![Button::Left]);
+    // Other keys are tracked separately
+    assert_eq!(keys.press(Button::Up, ms(720)), [ButtonEvent::Press]);
+    assert_eq!(keys.press(Button::Down, ms(730)), [ButtonEvent::Press]);
+}
+
+#[test]
+fn key_repeat_taps_accelerate_slider() {
+    let mut app = App::new();
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![make_slider_submenu("Slider", "slider")]),
+    }]);
+    app.on_a();
+    app.on_a();
+    // Every tap lands at the same moment, so only the streak length matters
+    app.selected_submenu()
+        .slider
+        .as_mut()
+        .unwrap()
+        .get_mut::<u32>()
+        .unwrap()
+        .accel = StepAcceleration::new(ms(300), 2).with_clock(|| Duration::ZERO);
+
+    // Taps 200ms apart each step the handle, and the third switches to the fast increment
+    let mut keys = filter();
+    for t in [0, 200, 400] {
+        for event in keys.press(Button::Right, ms(t)) {
+            app.handle_input(Button::Right, *event);
+        }
+    }
+    let slider = app.selected_submenu().slider.clone().unwrap();
+    assert_eq!(slider.get::<u32>().unwrap().lower, 12);
+}
@@ -0,0 +1,192 @@
+use training_mod_tui_2::{Button, Key, KeyAction, KeyChord, Keymap, KeymapError};
+
+#[test]
+fn key_chord_parse() {
+    assert_eq!(KeyChord::parse("a").unwrap(), KeyChord::char('a'));
+    assert_eq!(KeyChord::parse("A").unwrap(), KeyChord::char('A'));
+    assert_eq!(KeyChord::parse("shift+a").unwrap(), KeyChord::char('A'));
+    assert_eq!(KeyChord::parse("space").unwrap(), KeyChord::char(' '));
+    assert_eq!(KeyChord::parse("ENTER").unwrap(), KeyChord::new(Key::Enter));
+    assert_eq!(KeyChord::parse("F5").unwrap(), KeyChord::new(Key::F(5)));
+    assert_eq!(
+        KeyChord::parse("ctrl+h").unwrap(),
+        KeyChord::ctrl(Key::Char('h'))
+    );
+    assert_eq!(KeyChord::parse("+").unwrap(), KeyChord::char('+'));
+    assert_eq!(
+        KeyChord::parse("ctrl++").unwrap(),
+        KeyChord::ctrl(Key::Char('+'))
+    );
+    assert_eq!(
+        KeyChord::parse("ctrl+alt+shift+Left").unwrap(),
+        KeyChord {
+            key: Key::Left,
+            ctrl: true,
+            alt: true,
+            shift: true,
+        }
+    );
+    for invalid in ["", "F13", "F0", "hyper+a", "ctrl+", "notakey"] {
+        assert!(
+            matches!(KeyChord::parse(invalid), Err(KeymapError::InvalidKey(_))),
+            "{:?} should not parse",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn key_chord_display() {
+    assert_eq!(KeyChord::char('a').to_string(), "a");
+    assert_eq!(KeyChord::char(' ').to_string(), "Space");
+    assert_eq!(KeyChord::new(Key::PageUp).to_string(), "PageUp");
+    assert_eq!(KeyChord::new(Key::F(10)).to_string(), "F10");
+    assert_eq!(KeyChord::ctrl(Key::Char('s')).to_string(), "ctrl+s");
+    // Displayed names parse back to the same chord
+    for chord in [
+        KeyChord::char(' '),
+        KeyChord::new(Key::PageDown),
+        KeyChord::ctrl(Key::Left),
+    ] {
+        assert_eq!(KeyChord::parse(&chord.to_string()).unwrap(), chord);
+    }
+}
+
+#[test]
+fn keymap_presets() {
+    let names: Vec<String> = Keymap::presets().into_iter().map(|k| k.name).collect();
+    assert_eq!(names, vec!["Default", "Vim", "Arrows", "WASD"]);
+    assert_eq!(Keymap::default(), Keymap::default_preset());
+    assert_eq!(Keymap::preset("vim"), Some(Keymap::vim()));
+    assert_eq!(Keymap::preset("qwerty"), None);
+
+    // Every preset binds every action
+    for keymap in Keymap::presets() {
+        for (action, keys) in keymap.entries() {
+            assert!(
+                !keys.is_empty(),
+                "{} leaves {:?} unbound",
+                keymap.name,
+                action
+            );
+        }
+    }
+
+    let vim = Keymap::vim();
+    assert_eq!(
+        vim.action(KeyChord::char('j')),
+        Some(KeyAction::Press(Button::Down))
+    );
+    assert_eq!(
+        vim.action(KeyChord::new(Key::Down)),
+        Some(KeyAction::Press(Button::Down))
+    );
+    assert_eq!(
+        vim.action(KeyChord::char('?')),
+        Some(KeyAction::ShowBindings)
+    );
+    assert_eq!(vim.action(KeyChord::char('z')), None);
+    assert_eq!(vim.action(KeyChord::ctrl(Key::Char('j'))), None);
+}
+
+#[test]
+fn keymap_labels() {
+    let labels = Keymap::wasd().labels();
+    assert_eq!(labels.len(), Button::ALL.len());
+    assert_eq!(labels[&Button::A], "Space");
+    assert_eq!(labels[&Button::Up], "w");
+    assert_eq!(labels[&Button::Start], "Esc");
+}
+
+#[test]
+fn keymap_bind_moves_keys() {
+    let mut keymap = Keymap::default_preset();
+    keymap.bind(
+        KeyAction::Press(Button::ZL),
+        vec![KeyChord::char('a'), KeyChord::char('u')],
+    );
+    assert_eq!(
+        keymap.action(KeyChord::char('a')),
+        Some(KeyAction::Press(Button::ZL))
+    );
+    // A keeps its other key
+    assert_eq!(
+        keymap.keys(KeyAction::Press(Button::A)),
+        &[KeyChord::new(Key::Enter)]
+    );
+    assert_eq!(keymap.labels()[&Button::A], "Enter");
+}
+
+#[test]
+fn keymap_from_json() {
+    let json = r#"{
+        "name": "Mine",
+        "base": "vim",
+        "zl": ["ctrl+h", "u"],
+        "bindings": "F1"
+    }"#;
+    let keymap = Keymap::from_json(json).unwrap();
+    assert_eq!(keymap.name, "Mine");
+    assert_eq!(
+        keymap.keys(KeyAction::Press(Button::ZL)),
+        &[KeyChord::ctrl(Key::Char('h')), KeyChord::char('u')]
+    );
+    assert_eq!(
+        keymap.keys(KeyAction::ShowBindings),
+        &[KeyChord::new(Key::F(1))]
+    );
+    // Actions which aren't listed keep the base preset's keys
+    assert_eq!(
+        keymap.keys(KeyAction::Press(Button::Down)),
+        Keymap::vim().keys(KeyAction::Press(Button::Down))
+    );
+
+    // Without a base, the default preset is used
+    let keymap = Keymap::from_json(r#"{"a": "space"}"#).unwrap();
+    assert_eq!(keymap.name, "Default");
+    assert_eq!(
+        keymap.action(KeyChord::char(' ')),
+        Some(KeyAction::Press(Button::A))
+    );
+    assert_eq!(
+        keymap.action(KeyChord::char('x')),
+        Some(KeyAction::Press(Button::X))
+    );
+}
+
+#[test]
+fn keymap_from_json_errors() {
+    assert!(matches!(
+        Keymap::from_json("not json"),
+        Err(KeymapError::Json(_))
+    ));
+    assert!(matches!(
+        Keymap::from_json("[]"),
+        Err(KeymapError::NotAnObject)
+    ));
+    assert!(matches!(
+        Keymap::from_json(r#"{"base": "qwerty"}"#),
+        Err(KeymapError::UnknownBase(_))
+    ));
+    assert!(matches!(
+        Keymap::from_json(r#"{"base": 1}"#),
+        Err(KeymapError::NotAString(_))
+    ));
+    assert!(matches!(
+        Keymap::from_json(r#"{"jump": "space"}"#),
+        Err(KeymapError::UnknownAction(_))
+    ));
+    assert!(matches!(
+        Keymap::from_json(r#"{"a": ["space", 1]}"#),
+        Err(KeymapError::NotAString(_))
+    ));
+    assert!(matches!(
+        Keymap::from_json(r#"{"a": "ctrl+nothing"}"#),
+        Err(KeymapError::InvalidKey(_))
+    ));
+    #[cfg(feature = "std")]
+    assert!(matches!(
+        Keymap::load("does/not/exist.json"),
+        Err(KeymapError::Io(_))
+    ));
+}
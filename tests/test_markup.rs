@@ -0,0 +1,71 @@
+#![cfg(feature = "has_terminal")]
+
+use ratatui::{
+    style::{Color, Modifier},
+    text::{Line, Span},
+};
+use training_mod_tui_2::{parse_markup, Theme};
+
+#[test]
+fn markup_plain_text() {
+    let theme = Theme::default();
+    let lines = parse_markup("First line\nSecond line", &theme);
+    assert_eq!(
+        lines,
+        vec![
+            Line::from(Span::styled("First line", theme.text)),
+            Line::from(Span::styled("Second line", theme.text)),
+        ]
+    );
+}
+
+#[test]
+fn markup_bold_and_colour() {
+    let theme = Theme::default();
+    let lines = parse_markup("Press **now** or [red]never [blue]ever[/] again[/]", &theme);
+    let bold = theme.text.add_modifier(Modifier::BOLD);
+    assert_eq!(
+        lines,
+        vec![Line::from(vec![
+            Span::styled("Press ", theme.text),
+            Span::styled("now", bold),
+            Span::styled(" or ", theme.text),
+            Span::styled("never ", theme.text.fg(Color::Red)),
+            Span::styled("ever", theme.text.fg(Color::Blue)),
+            Span::styled(" again", theme.text.fg(Color::Red)),
+        ])]
+    );
+}
+
+#[test]
+fn markup_bullets_and_buttons() {
+    let theme = Theme::default();
+    let lines = parse_markup("- Press <a>\n  * Then <ZL>", &theme);
+    assert_eq!(
+        lines,
+        vec![
+            Line::from(vec![
+                Span::styled("• Press ", theme.text),
+                Span::styled(" A ", theme.button),
+            ]),
+            Line::from(vec![
+                Span::styled("  • Then ", theme.text),
+                Span::styled(" ZL ", theme.button),
+            ]),
+        ]
+    );
+}
+
+#[test]
+fn markup_literal_text() {
+    let theme = Theme::default();
+    // Unknown tags and escaped characters are kept as they are
+    let lines = parse_markup("[not a colour] <Q> \\*\\*not bold\\*\\* \\[red]", &theme);
+    assert_eq!(
+        lines,
+        vec![Line::from(Span::styled(
+            "[not a colour] <Q> **not bold** [red]",
+            theme.text
+        ))]
+    );
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "has_terminal")]
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Color,
+    widgets::{StatefulWidget, Widget},
+};
+use training_mod_tui_2::*;
+
+fn render(slider: &mut StatefulSlider, area: Rect, orientation: SliderOrientation) -> Buffer {
+    let mut buf = Buffer::empty(area);
+    RangeSlider::new(&slider.view(""))
+        .orientation(orientation)
+        .render(area, &mut buf);
+    buf
+}
+
+fn row(buf: &Buffer, y: u16) -> String {
+    (0..buf.area.width)
+        .map(|x| buf.get(x, y).symbol.clone())
+        .collect()
+}
+
+#[test]
+fn range_slider_horizontal_full_range() {
+    let mut slider = StatefulSlider::new();
+    let buf = render(
+        &mut slider,
+        Rect::new(0, 0, 16, 2),
+        SliderOrientation::Horizontal,
+    );
+    assert_eq!(row(&buf, 0), "█━━━━━━━━━━━━━━█");
+    // The limits share their positions with the value labels, so they aren't repeated
+    assert_eq!(row(&buf, 1), "0            150");
+}
+
+#[test]
+fn range_slider_horizontal_labels() {
+    let mut slider = StatefulSlider {
+        lower: 60,
+        upper: 90,
+        ..StatefulSlider::new()
+    };
+    let buf = render(
+        &mut slider,
+        Rect::new(0, 0, 16, 2),
+        SliderOrientation::Horizontal,
+    );
+    assert_eq!(row(&buf, 0), "──────█━━█──────");
+    assert_eq!(row(&buf, 1), "0    60 90   150");
+}
+
+#[test]
+fn range_slider_stateful() {
+    // A StatefulSlider can be drawn directly, just as its view would be
+    let mut slider = StatefulSlider {
+        lower: 60,
+        upper: 90,
+        ..StatefulSlider::new()
+    };
+    let area = Rect::new(0, 0, 16, 2);
+    let mut buf = Buffer::empty(area);
+    RangeSlider::default()
+        .stateful()
+        .render(area, &mut buf, &mut slider);
+    assert_eq!(
+        buf,
+        render(&mut slider, area, SliderOrientation::Horizontal)
+    );
+}
+
+#[test]
+fn range_slider_horizontal_zero_width() {
+    // Handles sharing a cell still show both labels, side by side
+    let mut slider = StatefulSlider {
+        lower: 75,
+        upper: 75,
+        ..StatefulSlider::new()
+    };
+    let buf = render(
+        &mut slider,
+        Rect::new(0, 0, 16, 2),
+        SliderOrientation::Horizontal,
+    );
+    assert_eq!(row(&buf, 0), "────────█───────");
+    assert_eq!(row(&buf, 1), "0     75 75  150");
+}
+
+#[test]
+fn range_slider_handle_styles() {
+    let mut slider = StatefulSlider {
+        lower: 60,
+        upper: 90,
+        ..StatefulSlider::new()
+    };
+    let area = Rect::new(0, 0, 16, 2);
+    let buf = render(&mut slider, area, SliderOrientation::Horizontal);
+    assert_eq!(buf.get(6, 0).fg, Color::Red);
+    assert_eq!(buf.get(9, 0).fg, Color::Yellow);
+
+    slider.state = SliderState::UpperSelected;
+    let buf = render(&mut slider, area, SliderOrientation::Horizontal);
+    assert_eq!(buf.get(6, 0).fg, Color::Yellow);
+    assert_eq!(buf.get(9, 0).fg, Color::Green);
+}
+
+#[test]
+fn range_slider_vertical() {
+    let mut slider = StatefulSlider::new();
+    let buf = render(
+        &mut slider,
+        Rect::new(0, 0, 6, 5),
+        SliderOrientation::Vertical,
+    );
+    assert_eq!(row(&buf, 0), "█ 150 ");
+    assert_eq!(row(&buf, 1), "┃     ");
+    assert_eq!(row(&buf, 2), "┃     ");
+    assert_eq!(row(&buf, 3), "┃     ");
+    assert_eq!(row(&buf, 4), "█ 0   ");
+}
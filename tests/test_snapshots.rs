@@ -0,0 +1,176 @@
+//! Renders the menu into an off-screen buffer and compares it against the text
+//! snapshots in `tests/snapshots`.
+//!
+//! A missing or different snapshot fails the test. When the layout changes on purpose,
+//! regenerate them with `UPDATE_SNAPSHOTS=1 cargo test --test test_snapshots`
+//! and review the diff before committing.
+#![cfg(feature = "has_terminal")]
+
+use std::path::PathBuf;
+use training_mod_tui_2::*;
+
+mod common;
+use common::*;
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+fn assert_snapshot(name: &str, app: &mut App) {
+    assert_snapshot_sized(name, app, WIDTH, HEIGHT);
+}
+
+fn assert_snapshot_sized(name: &str, app: &mut App, width: u16, height: u16) {
+    let actual = render_to_text(app, width, height);
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("Could not create snapshot dir!");
+        std::fs::write(&path, &actual).expect("Could not write snapshot!");
+        return;
+    }
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(_) => panic!(
+            "Snapshot {} is missing. Rerun with UPDATE_SNAPSHOTS=1 to create it.\n--- actual\n{}",
+            name, actual
+        ),
+    };
+    assert!(
+        expected == actual,
+        "Snapshot {} does not match. Rerun with UPDATE_SNAPSHOTS=1 if the change is intended.\n\
+         --- expected\n{}--- actual\n{}",
+        name,
+        expected,
+        actual
+    );
+}
+
+/// Opens the slider submenu of the first tab
+fn open_slider<'a>() -> App<'a> {
+    let mut app = initialize_tabbed_app();
+    app.selected_tab().submenus.select(1, 0);
+    app.on_a();
+    assert!(app.page == AppPage::SLIDER);
+    app
+}
+
+fn slider<'b>(app: &'b mut App) -> &'b mut StatefulSlider {
+    app.selected_submenu()
+        .slider
+        .as_mut()
+        .unwrap()
+        .get_mut()
+        .unwrap()
+}
+
+#[test]
+fn snapshot_submenu_page() {
+    let mut app = initialize_tabbed_app();
+    assert_snapshot("submenu_page", &mut app);
+}
+
+#[test]
+fn snapshot_tab_positions() {
+    let mut app = initialize_tabbed_app();
+    app.on_zr();
+    app.on_zr();
+    assert_snapshot("tab_middle", &mut app);
+    app.on_zl();
+    app.on_zl();
+    app.on_zl();
+    assert_snapshot("tab_last", &mut app);
+}
+
+#[test]
+fn snapshot_toggle_pages() {
+    let mut app = initialize_tabbed_app();
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+    assert_snapshot("toggle_single_page", &mut app);
+    app.on_b();
+    app.on_right();
+    app.on_a();
+    assert!(app.page == AppPage::TOGGLE);
+    assert_snapshot("toggle_multiple_page", &mut app);
+}
+
+#[test]
+fn snapshot_slider_states() {
+    let mut app = open_slider();
+    slider(&mut app).range_handle = true;
+    assert_snapshot("slider_lower_hover", &mut app);
+    app.on_a();
+    app.on_right();
+    assert_eq!(slider(&mut app).state, SliderState::LowerSelected);
+    assert_snapshot("slider_lower_selected", &mut app);
+    app.on_b();
+    app.on_right();
+    assert_snapshot("slider_upper_hover", &mut app);
+    app.on_a();
+    app.on_left();
+    assert_eq!(slider(&mut app).state, SliderState::UpperSelected);
+    assert_snapshot("slider_upper_selected", &mut app);
+    app.on_b();
+    app.on_right();
+    assert_eq!(slider(&mut app).state, SliderState::RangeHover);
+    assert_snapshot("slider_range_hover", &mut app);
+    app.on_a();
+    assert_eq!(slider(&mut app).state, SliderState::RangeSelected);
+    assert_snapshot("slider_range_selected", &mut app);
+    slider(&mut app).state = SliderState::None;
+    assert_snapshot("slider_none", &mut app);
+}
+
+#[test]
+fn snapshot_slider_numeric_entry() {
+    let mut app = open_slider();
+    app.on_right();
+    app.on_a();
+    app.on_x();
+    assert!(app.numeric_entry_active());
+    assert_snapshot("slider_numeric_entry", &mut app);
+}
+
+#[test]
+fn snapshot_slider_modes() {
+    let mut app = open_slider();
+    app.on_l();
+    assert_eq!(slider(&mut app).mode, SliderMode::Push);
+    assert_snapshot("slider_push", &mut app);
+    app.on_l();
+    assert_eq!(slider(&mut app).mode, SliderMode::LockedWidth);
+    assert_snapshot("slider_locked_width", &mut app);
+}
+
+#[test]
+fn snapshot_confirmation_page() {
+    let mut app = initialize_tabbed_app();
+    app.selected_tab().submenus.select(1, 1);
+    app.on_a();
+    assert!(app.page == AppPage::CONFIRMATION);
+    assert_snapshot("confirmation_page", &mut app);
+}
+
+#[test]
+fn snapshot_close_page() {
+    let mut app = initialize_tabbed_app();
+    app.page = AppPage::CLOSE;
+    assert_snapshot("close_page", &mut app);
+}
+
+#[test]
+fn snapshot_help_overlay() {
+    let mut app = initialize_tabbed_app();
+    app.on_y();
+    assert!(app.help_open);
+    assert_snapshot("help_overlay", &mut app);
+}
+
+#[test]
+fn snapshot_narrow_terminal() {
+    let mut app = initialize_tabbed_app();
+    assert_snapshot_sized("submenu_page_narrow", &mut app, 40, 24);
+    assert_snapshot_sized("too_small", &mut app, 20, 8);
+}
@@ -1,4 +1,11 @@
-use training_mod_tui_2::{SliderState, StatefulSlider};
+#![allow(clippy::bool_assert_comparison, clippy::redundant_field_names)]
+
+use std::cell::Cell;
+use std::time::Duration;
+use training_mod_tui_2::{
+    EntryKey, NumericEntry, SliderEntryError, SliderFormat, SliderMode, SliderState, SliderTick,
+    StatefulSlider, StepAcceleration,
+};
 
 fn initialize_slider(state: SliderState) -> StatefulSlider {
     StatefulSlider {
@@ -9,6 +16,13 @@ fn initialize_slider(state: SliderState) -> StatefulSlider {
         max: 150,
         incr_amount_slow: 1,
         incr_amount_fast: 10,
+        accel: StepAcceleration::default(),
+        precision: None,
+        format: SliderFormat::default(),
+        mode: SliderMode::Clamp,
+        range_handle: false,
+        presets: vec![],
+        entry: None,
     }
 }
 
@@ -194,6 +208,70 @@ fn stateful_slider_decrement_selected_fast() {
     assert_eq!(s.upper, s.lower);
 }
 
+thread_local! {
+    static NOW: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+fn test_clock() -> Duration {
+    NOW.with(|now| now.get())
+}
+
+fn advance_clock(by: Duration) {
+    NOW.with(|now| now.set(now.get() + by));
+}
+
+#[test]
+fn stateful_slider_increment_selected_fast_after_streak() {
+    let mut s = initialize_slider(SliderState::UpperSelected);
+    s.accel = StepAcceleration::new(Duration::from_millis(300), 2).with_clock(test_clock);
+    s.lower = 0;
+    s.upper = 50;
+
+    // The first presses of a streak use the slow increment, then it switches to the fast one
+    s.decrement_selected();
+    assert_eq!(s.upper, 49);
+    s.decrement_selected();
+    assert_eq!(s.upper, 48);
+    s.decrement_selected();
+    assert_eq!(s.upper, 38);
+    s.decrement_selected();
+    assert_eq!(s.upper, 28);
+
+    // Changing direction starts a new streak
+    s.increment_selected();
+    assert_eq!(s.upper, 29);
+
+    // Changing handle starts a new streak
+    s.state = SliderState::LowerSelected;
+    s.increment_selected();
+    assert_eq!(s.lower, 1);
+}
+
+#[test]
+fn stateful_slider_increment_selected_window() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.accel = StepAcceleration::new(Duration::from_millis(300), 1).with_clock(test_clock);
+    s.upper = 150;
+
+    // Presses further apart than the window never accelerate
+    for expected in 1..=3 {
+        advance_clock(Duration::from_millis(301));
+        s.increment_selected();
+        assert_eq!(s.lower, expected);
+    }
+
+    // Presses within the window do
+    advance_clock(Duration::from_millis(300));
+    s.increment_selected();
+    assert_eq!(s.lower, 13);
+
+    // Zero presses disables acceleration
+    s.accel = StepAcceleration::new(Duration::from_millis(300), 0).with_clock(test_clock);
+    s.increment_selected();
+    s.increment_selected();
+    assert_eq!(s.lower, 15);
+}
+
 #[test]
 fn stateful_slider_select_deselect() {
     let mut s = initialize_slider(SliderState::LowerHover);
@@ -286,3 +364,438 @@ fn stateful_slider_serialize() {
     let s_json = serde_json::to_string(&s).unwrap();
     assert_eq!(&s_json, "[25,75]");
 }
+
+#[test]
+fn stateful_slider_negative_range() {
+    let mut s: StatefulSlider<i32> = StatefulSlider::with_range(-180, 180, 1, 45);
+    assert_eq!(s.lower, -180);
+    assert_eq!(s.upper, 180);
+
+    // Check LowerSelected: lower can't go below min
+    s.state = SliderState::LowerSelected;
+    s.decrement_selected_fast();
+    assert_eq!(s.lower, -180);
+    s.increment_selected_fast();
+    assert_eq!(s.lower, -135);
+
+    // Check UpperSelected: upper can't go below lower
+    s.state = SliderState::UpperSelected;
+    s.upper = -130;
+    s.decrement_selected_fast();
+    assert_eq!(s.upper, -135);
+    assert_eq!(s.format_value(s.upper), "-135");
+}
+
+#[test]
+fn stateful_slider_float() {
+    let mut s: StatefulSlider<f32> = StatefulSlider::with_range(-1.0, 1.0, 0.25, 0.5);
+    assert_eq!(s.decimals(), 2);
+
+    // Check LowerSelected: lower can't go above upper
+    s.state = SliderState::LowerSelected;
+    s.upper = 0.5;
+    s.increment_selected_slow();
+    assert_eq!(s.lower, -0.75);
+    s.increment_selected_fast();
+    s.increment_selected_fast();
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 0.5);
+
+    // Check UpperSelected: upper can't go above max
+    s.state = SliderState::UpperSelected;
+    s.increment_selected_fast();
+    s.increment_selected_fast();
+    assert_eq!(s.upper, 1.0);
+
+    assert_eq!(s.format_value(s.lower), "0.50");
+    s.precision = Some(1);
+    assert_eq!(s.format_value(s.upper), "1.0");
+
+    let json = serde_json::to_string(&s).unwrap();
+    assert_eq!(json, "[0.5,1.0]");
+}
+
+#[test]
+#[should_panic]
+fn stateful_slider_with_range_invalid() {
+    StatefulSlider::with_range(10, 0, 1, 5);
+}
+
+fn make_ticks() -> Vec<SliderTick<u32>> {
+    [(0, "Fresh"), (60, "Mid"), (100, "Kill %")]
+        .into_iter()
+        .map(|(value, label)| SliderTick {
+            value,
+            label: label.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn stateful_slider_format_value() {
+    let mut s = initialize_slider(SliderState::LowerHover);
+    assert_eq!(s.format_value(40), "40");
+
+    s.format.unit = "%".to_string();
+    assert_eq!(s.format_value(40), "40%");
+
+    // 90 frames at 60 fps is 1.5 seconds. Seconds are shown to two decimal places by default,
+    // so that frame counts under a second don't round to a whole one.
+    s.format.unit = "s".to_string();
+    s.format.frames_per_second = Some(60);
+    assert_eq!(s.format_value(90), "1.50s");
+    assert_eq!(s.format_value(45), "0.75s");
+    s.precision = Some(1);
+    assert_eq!(s.format_value(45), "0.8s");
+}
+
+#[test]
+fn stateful_slider_snap_to_ticks() {
+    let mut s = initialize_slider(SliderState::UpperSelected);
+    s.format.ticks = make_ticks();
+
+    // Without snapping, ticks don't affect movement
+    s.lower = 5;
+    s.upper = 20;
+    s.increment_selected_slow();
+    assert_eq!(s.upper, 21);
+
+    // Snapping moves to the next tick in the direction of travel
+    s.format.snap_to_ticks = true;
+    s.increment_selected_slow();
+    assert_eq!(s.upper, 60);
+    s.increment_selected_fast();
+    assert_eq!(s.upper, 100);
+
+    // Past the last tick, the increment amounts are used
+    s.increment_selected_fast();
+    assert_eq!(s.upper, 110);
+    s.decrement_selected_slow();
+    assert_eq!(s.upper, 100);
+
+    // Snapping still respects the other handle
+    s.state = SliderState::LowerSelected;
+    s.decrement_selected_slow();
+    assert_eq!(s.lower, 0);
+    s.upper = 50;
+    s.increment_selected_slow();
+    assert_eq!(s.lower, 50);
+}
+
+#[test]
+fn stateful_slider_push_mode() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.mode = SliderMode::Push;
+    s.lower = 45;
+    s.upper = 50;
+
+    // Lower pushes upper along
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 55);
+    assert_eq!(s.upper, 55);
+
+    // Lower can't go above max
+    s.lower = 145;
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 150);
+    assert_eq!(s.upper, 150);
+
+    // Upper pushes lower along
+    s.state = SliderState::UpperSelected;
+    s.lower = 100;
+    s.upper = 105;
+    s.decrement_selected_fast();
+    assert_eq!(s.lower, 95);
+    assert_eq!(s.upper, 95);
+
+    // Upper can't go below min
+    s.lower = 3;
+    s.upper = 5;
+    s.decrement_selected_fast();
+    assert_eq!(s.lower, 0);
+    assert_eq!(s.upper, 0);
+}
+
+#[test]
+fn stateful_slider_locked_width_mode() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.mode = SliderMode::LockedWidth;
+    s.lower = 20;
+    s.upper = 50;
+
+    // Either handle slides the whole range
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 30);
+    assert_eq!(s.upper, 60);
+    s.state = SliderState::UpperSelected;
+    s.decrement_selected_slow();
+    assert_eq!(s.lower, 29);
+    assert_eq!(s.upper, 59);
+
+    // The range stops at the limits without shrinking
+    s.upper = 145;
+    s.lower = 115;
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 120);
+    assert_eq!(s.upper, 150);
+    s.lower = 5;
+    s.upper = 35;
+    s.decrement_selected_fast();
+    assert_eq!(s.lower, 0);
+    assert_eq!(s.upper, 30);
+}
+
+#[test]
+fn stateful_slider_range_handle() {
+    let mut s = initialize_slider(SliderState::UpperHover);
+    s.range_handle = true;
+    s.lower = 20;
+    s.upper = 50;
+
+    s.switch_hover();
+    assert_eq!(s.state, SliderState::RangeHover);
+    assert_eq!(s.is_handle_selected(), false);
+    s.select_deselect();
+    assert_eq!(s.state, SliderState::RangeSelected);
+    assert_eq!(s.is_handle_selected(), true);
+
+    // The range moves as one, in any mode
+    s.increment_selected_fast();
+    assert_eq!(s.lower, 30);
+    assert_eq!(s.upper, 60);
+    s.decrement_selected_slow();
+    assert_eq!(s.lower, 29);
+    assert_eq!(s.upper, 59);
+
+    s.deselect();
+    assert_eq!(s.state, SliderState::RangeHover);
+    s.switch_hover();
+    assert_eq!(s.state, SliderState::LowerHover);
+}
+
+#[test]
+fn stateful_slider_presets() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.presets = vec![0, 30, 60, 90, 120, 150];
+    s.lower = 20;
+    s.upper = 100;
+
+    s.next_preset();
+    assert_eq!(s.lower, 30);
+    s.next_preset();
+    s.next_preset();
+    assert_eq!(s.lower, 90);
+    // 120 and 150 would cross the upper handle, so we wrap around to 0
+    s.next_preset();
+    assert_eq!(s.lower, 0);
+    s.previous_preset();
+    assert_eq!(s.lower, 90);
+
+    // In push mode, the upper handle is pushed along instead
+    s.mode = SliderMode::Push;
+    s.next_preset();
+    assert_eq!(s.lower, 120);
+    assert_eq!(s.upper, 120);
+
+    // Presets do nothing without a selected handle
+    s.state = SliderState::LowerHover;
+    s.next_preset();
+    assert_eq!(s.lower, 120);
+}
+
+#[test]
+fn stateful_slider_set_selected() {
+    let mut s: StatefulSlider<i32> = StatefulSlider::with_range(-50, 50, 1, 10);
+    s.upper = 10;
+    assert_eq!(s.set_selected(0), Err(SliderEntryError::NoHandleSelected));
+    s.state = SliderState::LowerSelected;
+    assert_eq!(s.set_selected(-51), Err(SliderEntryError::BelowMinimum));
+    assert_eq!(s.set_selected(51), Err(SliderEntryError::AboveMaximum));
+    assert_eq!(
+        s.set_selected(20),
+        Err(SliderEntryError::CrossesOtherHandle)
+    );
+    assert_eq!(s.lower, -50);
+    assert_eq!(s.set_selected(-20), Ok(()));
+    assert_eq!(s.lower, -20);
+}
+
+#[test]
+fn stateful_slider_set_selected_locked_width() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.mode = SliderMode::LockedWidth;
+    s.presets = vec![0, 30, 60, 90, 120, 150];
+    s.lower = 20;
+    s.upper = 60;
+
+    // The whole range moves instead of just the selected handle
+    assert_eq!(s.set_selected(50), Ok(()));
+    assert_eq!((s.lower, s.upper), (50, 90));
+    assert_eq!(s.set_selected(140), Ok(()));
+    assert_eq!((s.lower, s.upper), (110, 150));
+    assert_eq!(s.set_selected(151), Err(SliderEntryError::AboveMaximum));
+    assert_eq!((s.lower, s.upper), (110, 150));
+
+    // Presets and typed values keep the width too
+    s.previous_preset();
+    assert_eq!((s.lower, s.upper), (90, 130));
+    s.begin_entry();
+    for digit in [1, 0] {
+        s.entry.as_mut().unwrap().type_key(EntryKey::Digit(digit));
+    }
+    assert_eq!(s.commit_entry(), Ok(()));
+    assert_eq!((s.lower, s.upper), (10, 50));
+
+    // Presets the range can't move towards are skipped
+    s.lower = 110;
+    s.upper = 150;
+    s.next_preset();
+    assert_eq!((s.lower, s.upper), (0, 40));
+}
+
+#[test]
+fn stateful_slider_numeric_entry_typing() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.lower = 20;
+    s.upper = 100;
+
+    s.begin_entry();
+    let entry = s.entry.unwrap();
+    assert_eq!(entry.digits(), &[0, 2, 0]);
+    assert_eq!(entry.has_sign, false);
+
+    // Digits shift in from the right
+    for digit in [1, 2, 0] {
+        s.entry.as_mut().unwrap().type_key(EntryKey::Digit(digit));
+    }
+    assert_eq!(s.entry.unwrap().value(), 120.0);
+    assert_eq!(s.commit_entry(), Err(SliderEntryError::CrossesOtherHandle));
+    assert_eq!(
+        s.entry.unwrap().error,
+        Some(SliderEntryError::CrossesOtherHandle)
+    );
+    assert_eq!(s.lower, 20);
+
+    s.entry.as_mut().unwrap().type_key(EntryKey::Backspace);
+    assert_eq!(s.entry.unwrap().error, None);
+    assert_eq!(s.commit_entry(), Ok(()));
+    assert_eq!(s.lower, 12);
+    assert_eq!(s.entry, None);
+
+    // Cancelling leaves the value alone
+    s.begin_entry();
+    s.entry.as_mut().unwrap().type_key(EntryKey::Digit(5));
+    s.cancel_entry();
+    assert_eq!(s.lower, 12);
+    assert_eq!(s.is_entering(), false);
+}
+
+#[test]
+fn stateful_slider_numeric_entry_dialing() {
+    let mut s: StatefulSlider<i32> = StatefulSlider::with_range(-180, 180, 1, 45);
+    s.state = SliderState::UpperSelected;
+    s.begin_entry();
+    let entry = s.entry.as_mut().unwrap();
+    assert_eq!(entry.has_sign, true);
+    assert_eq!(entry.digits(), &[1, 8, 0]);
+
+    // Slot 0 is the sign, and the cursor wraps around
+    entry.dial_up();
+    assert_eq!(entry.value(), -180.0);
+    entry.cursor_left();
+    assert_eq!(entry.cursor, 3);
+    entry.dial_up();
+    entry.cursor_left();
+    entry.dial_down();
+    assert_eq!(entry.value(), -171.0);
+    assert_eq!(s.commit_entry(), Ok(()));
+    assert_eq!(s.upper, -171);
+
+    s.state = SliderState::LowerSelected;
+    s.entry = None;
+    s.begin_entry();
+    s.entry.as_mut().unwrap().type_key(EntryKey::Digit(9));
+    assert_eq!(s.entry.unwrap().value(), -809.0);
+    assert_eq!(s.commit_entry(), Err(SliderEntryError::BelowMinimum));
+    s.entry.as_mut().unwrap().type_key(EntryKey::Minus);
+    assert_eq!(s.commit_entry(), Err(SliderEntryError::AboveMaximum));
+}
+
+#[test]
+fn stateful_slider_precision_limits() {
+    let mut s: StatefulSlider<f64> = StatefulSlider::with_range(0.0, 1.0, 0.1, 0.5);
+    s.precision = Some(40);
+    assert_eq!(s.decimals(), 9);
+    assert_eq!(s.format_value(0.5), "0.500000000");
+
+    // Entries can't have more decimal places than digits
+    let entry = NumericEntry::new(0.5, 1.0, 20, false);
+    assert_eq!(entry.len, 12);
+    assert_eq!(entry.decimals, 12);
+    assert_eq!(entry.value(), 0.5);
+}
+
+#[test]
+fn numeric_entry_decimals() {
+    let mut entry = NumericEntry::new(0.5, 1.0, 2, true);
+    assert_eq!(entry.digits(), &[0, 5, 0]);
+    entry.type_key(EntryKey::Digit(7));
+    assert_eq!(entry.value(), 5.07);
+}
+
+#[test]
+fn stateful_slider_mouse_drag() {
+    let mut s = initialize_slider(SliderState::LowerHover);
+    s.lower = 30;
+    s.upper = 90;
+    assert_eq!(s.value_at(0.0), 0);
+    assert_eq!(s.value_at(0.5), 75);
+    assert_eq!(s.value_at(2.0), 150);
+
+    // Grab the handle closest to where the slider was pressed
+    s.grab_nearest(80);
+    assert_eq!(s.state, SliderState::UpperSelected);
+    s.grab_nearest(50);
+    assert_eq!(s.state, SliderState::LowerSelected);
+
+    // Dragging past the other handle stops at it
+    s.drag_selected_to(10);
+    assert_eq!(s.lower, 10);
+    s.drag_selected_to(120);
+    assert_eq!((s.lower, s.upper), (90, 90));
+
+    // With the handles together, the handle on the side being moved towards is grabbed
+    s.grab_nearest(140);
+    assert_eq!(s.state, SliderState::UpperSelected);
+    s.grab_nearest(20);
+    assert_eq!(s.state, SliderState::LowerSelected);
+}
+
+#[test]
+fn stateful_slider_mouse_drag_modes() {
+    let mut s = initialize_slider(SliderState::LowerSelected);
+    s.lower = 30;
+    s.upper = 90;
+
+    // Push mode carries the other handle along
+    s.mode = SliderMode::Push;
+    s.drag_selected_to(120);
+    assert_eq!((s.lower, s.upper), (120, 120));
+
+    // Locked width mode keeps the distance between the handles, stopping at the limits
+    s.lower = 30;
+    s.upper = 90;
+    s.mode = SliderMode::LockedWidth;
+    s.drag_selected_to(50);
+    assert_eq!((s.lower, s.upper), (50, 110));
+    s.drag_selected_to(140);
+    assert_eq!((s.lower, s.upper), (90, 150));
+    s.state = SliderState::UpperSelected;
+    s.drag_selected_to(0);
+    assert_eq!((s.lower, s.upper), (0, 60));
+
+    // Dragging past the limits stops at them
+    s.mode = SliderMode::Clamp;
+    s.drag_selected_to(500);
+    assert_eq!(s.upper, 150);
+}
@@ -1,5 +1,4 @@
-use ratatui::widgets::{TableSelection, TableState};
-use training_mod_tui_2::StatefulTable;
+use training_mod_tui_2::{StatefulTable, TableSelection, TableState};
 
 fn initialize_table(row: usize, col: usize) -> StatefulTable<u8> {
     let mut s = StatefulTable::with_items(2, 3, vec![0, 1, 2, 3, 4]);
@@ -174,6 +173,7 @@ fn stateful_table_new() {
         items: vec![vec![None; 3]; 2],
         rows: 2,
         cols: 3,
+        preferred_cols: 3,
     };
     assert_eq!(t, u);
     assert_eq!(t, v);
@@ -187,6 +187,7 @@ fn stateful_table_with_items() {
         items: vec![vec![Some(1), Some(2), None], vec![None; 3]],
         rows: 2,
         cols: 3,
+        preferred_cols: 3,
     };
     assert_eq!(t, u);
 }
@@ -340,3 +341,80 @@ fn stateful_table_iter_mut() {
     assert_eq!(t_iter.next(), None);
     assert_eq!(t_iter.next(), None);
 }
+
+#[test]
+fn stateful_table_reflow() {
+    let mut t = initialize_table(0, 1);
+    t.reflow(2);
+    assert_eq!(t.rows, 3);
+    assert_eq!(t.cols, 2);
+    assert_eq!(
+        t.items,
+        vec![
+            vec![Some(0), Some(1)],
+            vec![Some(2), Some(3)],
+            vec![Some(4), None]
+        ]
+    );
+    assert_eq!(t.state, tablestate_with(0, 1));
+
+    // Navigation follows the new layout
+    t.next_row();
+    assert_eq!(t.get_selected(), Some(&mut 3));
+
+    // The table can't grow past the columns it was created with
+    t.reflow(10);
+    assert_eq!(t.cols, 3);
+    assert_eq!(t.rows, 2);
+    assert_eq!(t.get_selected(), Some(&mut 3));
+    assert_eq!(t.state, tablestate_with(1, 0));
+
+    t.reflow(0);
+    assert_eq!(t.cols, 1);
+    assert_eq!(t.rows, 5);
+    assert_eq!(t.get_selected(), Some(&mut 3));
+    assert_eq!(t.preferred_cols, 3);
+}
+
+#[test]
+fn stateful_table_reflow_gaps() {
+    let mut t: StatefulTable<u8> = StatefulTable::new(2, 3);
+    t.items = vec![vec![Some(0), None, Some(1)], vec![Some(2), Some(3), None]];
+    t.select(0, 2);
+    t.reflow(2);
+    // Gaps are dropped, and the same item stays selected
+    assert_eq!(
+        t.items,
+        vec![vec![Some(0), Some(1)], vec![Some(2), Some(3)]]
+    );
+    assert_eq!(t.get_selected(), Some(&mut 1));
+}
+
+#[test]
+fn stateful_table_select_nearest() {
+    let mut t = initialize_table(0, 0);
+    t.select_nearest(0, 2);
+    assert_eq!(t.state, tablestate_with(0, 2));
+    // Past the last item
+    t.select_nearest(1, 2);
+    assert_eq!(t.state, tablestate_with(1, 1));
+    // Outside the table
+    t.select_nearest(7, 9);
+    assert_eq!(t.state, tablestate_with(1, 1));
+    t.select_nearest(0, 9);
+    assert_eq!(t.state, tablestate_with(0, 2));
+
+    // Gaps select the nearest item before them
+    let mut gaps: StatefulTable<u8> = StatefulTable::new(2, 3);
+    gaps.items = vec![vec![None, Some(0), None], vec![Some(1), None, None]];
+    gaps.select_nearest(0, 2);
+    assert_eq!(gaps.get_selected(), Some(&mut 0));
+    gaps.select_nearest(1, 2);
+    assert_eq!(gaps.get_selected(), Some(&mut 1));
+    // Or the first item, if there's nothing before them
+    gaps.select_nearest(0, 0);
+    assert_eq!(gaps.get_selected(), Some(&mut 0));
+
+    let mut empty: StatefulTable<u8> = StatefulTable::new(0, 0);
+    empty.select_nearest(1, 1);
+}
@@ -1,4 +1,5 @@
-use ratatui::widgets::{TableSelection, TableState};
+#![allow(clippy::redundant_field_names)]
+
 use training_mod_tui_2::*;
 
 fn make_toggle<'a>(v: u8) -> Toggle<'a> {
@@ -6,6 +7,7 @@ fn make_toggle<'a>(v: u8) -> Toggle<'a> {
         title: "Title",
         value: v,
         max: 4,
+        help_text: "",
     }
 }
 
@@ -29,6 +31,11 @@ fn make_toggle_table_single<'a>(rows: usize, cols: usize, num: usize) -> Statefu
     t
 }
 
+/// A copy of the submenu's u32 slider
+fn slider(submenu: &SubMenu) -> StatefulSlider {
+    submenu.slider.as_ref().unwrap().get().unwrap().clone()
+}
+
 fn initialize_submenu<'a>(submenu_type: SubMenuType) -> SubMenu<'a> {
     match submenu_type {
         SubMenuType::ToggleSingle => SubMenu {
@@ -38,6 +45,7 @@ fn initialize_submenu<'a>(submenu_type: SubMenuType) -> SubMenu<'a> {
             submenu_type: submenu_type,
             toggles: make_toggle_table_single(2, 3, 4),
             slider: None,
+            submenus: None,
         },
         SubMenuType::ToggleMultiple => SubMenu {
             title: "Multi Option Menu",
@@ -46,6 +54,7 @@ fn initialize_submenu<'a>(submenu_type: SubMenuType) -> SubMenu<'a> {
             submenu_type: submenu_type,
             toggles: make_toggle_table_multiple(2, 3, 4),
             slider: None,
+            submenus: None,
         },
         SubMenuType::Slider => SubMenu {
             title: "Slider Menu",
@@ -53,9 +62,26 @@ fn initialize_submenu<'a>(submenu_type: SubMenuType) -> SubMenu<'a> {
             help_text: "A Double-ended Slider",
             submenu_type: submenu_type,
             toggles: make_toggle_table_multiple(0, 0, 0),
-            slider: Some(StatefulSlider::new()),
+            slider: Some(StatefulSlider::new().into()),
+            submenus: None,
+        },
+        SubMenuType::Nested => SubMenu {
+            title: "Nested Menu",
+            id: "nested",
+            help_text: "A Nested Menu",
+            submenu_type: submenu_type,
+            toggles: make_toggle_table_multiple(0, 0, 0),
+            slider: None,
+            submenus: Some(StatefulTable::with_items(
+                1,
+                2,
+                vec![
+                    initialize_submenu(SubMenuType::ToggleSingle),
+                    initialize_submenu(SubMenuType::Slider),
+                ],
+            )),
         },
-        SubMenuType::None => {
+        _ => {
             panic!()
         }
     }
@@ -74,6 +100,45 @@ fn submenu_serialize() {
     let submenu = initialize_submenu(SubMenuType::Slider);
     let json = serde_json::to_string(&submenu).unwrap();
     assert_eq!(&json, "[0,150]");
+
+    let submenu = initialize_submenu(SubMenuType::Nested);
+    let json = serde_json::to_string(&submenu).unwrap();
+    assert_eq!(&json, "{\"single_option\":[1,0,0,0],\"slider\":[0,150]}");
+}
+
+#[test]
+fn submenu_update_from_value() {
+    let mut submenu = initialize_submenu(SubMenuType::Nested);
+    let value = serde_json::json!({
+        "single_option": [0, 1, 0, 0],
+        "slider": [20, 80],
+    });
+    submenu.update_from_value(&value);
+    let json = serde_json::to_string(&submenu).unwrap();
+    assert_eq!(&json, "{\"single_option\":[0,1,0,0],\"slider\":[20,80]}");
+}
+
+#[test]
+fn submenu_slider_types() {
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    let mut slider = StatefulSlider::with_range(-10i16, 10, 1, 5);
+    slider.lower = -3;
+    submenu.slider = Some(slider.into());
+    assert_eq!(serde_json::to_string(&submenu).unwrap(), "[-3,10]");
+    assert_eq!(submenu.value_summary(), "-3–10");
+
+    // Values are parsed in the slider's own type
+    submenu.update_from_value(&serde_json::json!([-8, 4]));
+    let slider = submenu.slider.unwrap();
+    assert_eq!(slider.slider_type(), SliderType::I16);
+    let slider = slider.get::<i16>().unwrap();
+    assert_eq!((slider.lower, slider.upper), (-8, 4));
+
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    submenu.slider = Some(StatefulSlider::with_range(0.0f64, 1.0, 0.25, 0.5).into());
+    submenu.update_from_value(&serde_json::json!([0.25, 0.75]));
+    assert_eq!(serde_json::to_string(&submenu).unwrap(), "[0.25,0.75]");
+    assert_eq!(submenu.value_summary(), "0.25–0.75");
 }
 
 #[test]
@@ -130,13 +195,13 @@ fn submenu_update_from_vec() {
 
     let mut submenu = initialize_submenu(SubMenuType::Slider);
     let mut slider = StatefulSlider::new();
-    assert_eq!(submenu.slider, Some(slider));
+    assert_eq!(submenu.slider, Some(slider.clone().into()));
     slider.lower = 5;
     submenu.update_from_vec(vec![5, 150]);
-    assert_eq!(submenu.slider, Some(slider));
+    assert_eq!(submenu.slider, Some(slider.clone().into()));
     slider.upper = 75;
     submenu.update_from_vec(vec![5, 75]);
-    assert_eq!(submenu.slider, Some(slider));
+    assert_eq!(submenu.slider, Some(slider.clone().into()));
 }
 
 #[test]
@@ -198,41 +263,50 @@ fn submenu_multiple_on_a() {
 #[test]
 fn submenu_slider_on_a() {
     let mut submenu = initialize_submenu(SubMenuType::Slider);
-    assert_eq!(submenu.slider.unwrap().state, SliderState::LowerHover);
+    assert_eq!(slider(&submenu).state, SliderState::LowerHover);
     submenu.on_a();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::LowerSelected);
+    assert_eq!(slider(&submenu).state, SliderState::LowerSelected);
     submenu.on_a();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::LowerHover);
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::UpperHover,
-        ..submenu.slider.unwrap()
-    });
-    assert_eq!(submenu.slider.unwrap().state, SliderState::UpperHover);
+    assert_eq!(slider(&submenu).state, SliderState::LowerHover);
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::UpperHover,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
+    assert_eq!(slider(&submenu).state, SliderState::UpperHover);
     submenu.on_a();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::UpperSelected);
+    assert_eq!(slider(&submenu).state, SliderState::UpperSelected);
     submenu.on_a();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::UpperHover);
+    assert_eq!(slider(&submenu).state, SliderState::UpperHover);
 }
 
 #[test]
 fn submenu_slider_on_b_selected() {
     let mut submenu = initialize_submenu(SubMenuType::Slider);
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::LowerSelected,
-        ..submenu.slider.unwrap()
-    });
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::LowerSelected,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     submenu.on_b();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::LowerHover);
+    assert_eq!(slider(&submenu).state, SliderState::LowerHover);
     submenu.on_b();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::LowerHover);
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::UpperSelected,
-        ..submenu.slider.unwrap()
-    });
+    assert_eq!(slider(&submenu).state, SliderState::LowerHover);
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::UpperSelected,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     submenu.on_b();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::UpperHover);
+    assert_eq!(slider(&submenu).state, SliderState::UpperHover);
     submenu.on_b();
-    assert_eq!(submenu.slider.unwrap().state, SliderState::UpperHover);
+    assert_eq!(slider(&submenu).state, SliderState::UpperHover);
 }
 
 #[test]
@@ -274,6 +348,51 @@ fn submenu_multiple_on_up() {
     submenu.on_up();
     assert_eq!(submenu.toggles.state, state);
 }
+#[test]
+fn submenu_slider_on_up() {
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    submenu.slider = Some(
+        StatefulSlider {
+            lower: 20,
+            upper: 100,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
+    // Hovering: no change
+    submenu.on_up();
+    assert_eq!(slider(&submenu).lower, 20);
+
+    submenu.on_a();
+    submenu.on_up();
+    assert_eq!(slider(&submenu).lower, 30);
+    submenu.on_up();
+    assert_eq!(slider(&submenu).lower, 40);
+}
+
+#[test]
+fn submenu_slider_on_down() {
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::UpperHover,
+            lower: 20,
+            upper: 100,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
+    // Hovering: no change
+    submenu.on_down();
+    assert_eq!(slider(&submenu).upper, 100);
+
+    submenu.on_a();
+    submenu.on_down();
+    assert_eq!(slider(&submenu).upper, 90);
+    submenu.on_down();
+    assert_eq!(slider(&submenu).upper, 80);
+}
+
 #[test]
 fn submenu_single_on_down() {
     let mut submenu = initialize_submenu(SubMenuType::ToggleSingle);
@@ -370,41 +489,47 @@ fn submenu_multiple_on_left() {
 fn submenu_slider_on_left() {
     let mut submenu = initialize_submenu(SubMenuType::Slider);
     let mut state = SliderState::LowerHover;
-    assert_eq!(submenu.slider.unwrap().state, state);
+    assert_eq!(slider(&submenu).state, state);
     state = SliderState::UpperHover;
     submenu.on_left();
-    assert_eq!(submenu.slider.unwrap().state, state);
+    assert_eq!(slider(&submenu).state, state);
 
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::LowerSelected,
-        lower: 1,
-        ..submenu.slider.unwrap()
-    });
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::LowerSelected,
+            lower: 1,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     state = SliderState::LowerSelected;
     submenu.on_left();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 0);
-    assert_eq!(submenu.slider.unwrap().upper, 150);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 0);
+    assert_eq!(slider(&submenu).upper, 150);
     submenu.on_left();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 0);
-    assert_eq!(submenu.slider.unwrap().upper, 150);
-
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::UpperSelected,
-        lower: 99,
-        upper: 100,
-        ..submenu.slider.unwrap()
-    });
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 0);
+    assert_eq!(slider(&submenu).upper, 150);
+
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::UpperSelected,
+            lower: 99,
+            upper: 100,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     state = SliderState::UpperSelected;
     submenu.on_left();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 99);
-    assert_eq!(submenu.slider.unwrap().upper, 99);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 99);
+    assert_eq!(slider(&submenu).upper, 99);
     submenu.on_left();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 99);
-    assert_eq!(submenu.slider.unwrap().upper, 99);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 99);
+    assert_eq!(slider(&submenu).upper, 99);
 }
 
 #[test]
@@ -463,40 +588,152 @@ fn submenu_multiple_on_right() {
 fn submenu_slider_on_right() {
     let mut submenu = initialize_submenu(SubMenuType::Slider);
     let mut state = SliderState::LowerHover;
-    assert_eq!(submenu.slider.unwrap().state, state);
+    assert_eq!(slider(&submenu).state, state);
     state = SliderState::UpperHover;
     submenu.on_right();
-    assert_eq!(submenu.slider.unwrap().state, state);
-
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::LowerSelected,
-        lower: 10,
-        upper: 11,
-        ..submenu.slider.unwrap()
-    });
+    assert_eq!(slider(&submenu).state, state);
+
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::LowerSelected,
+            lower: 10,
+            upper: 11,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     state = SliderState::LowerSelected;
     submenu.on_right();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 11);
-    assert_eq!(submenu.slider.unwrap().upper, 11);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 11);
+    assert_eq!(slider(&submenu).upper, 11);
     submenu.on_right();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 11);
-    assert_eq!(submenu.slider.unwrap().upper, 11);
-
-    submenu.slider = Some(StatefulSlider {
-        state: SliderState::UpperSelected,
-        lower: 100,
-        upper: 149,
-        ..submenu.slider.unwrap()
-    });
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 11);
+    assert_eq!(slider(&submenu).upper, 11);
+
+    submenu.slider = Some(
+        StatefulSlider {
+            state: SliderState::UpperSelected,
+            lower: 100,
+            upper: 149,
+            ..slider(&submenu)
+        }
+        .into(),
+    );
     state = SliderState::UpperSelected;
     submenu.on_right();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 100);
-    assert_eq!(submenu.slider.unwrap().upper, 150);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 100);
+    assert_eq!(slider(&submenu).upper, 150);
     submenu.on_right();
-    assert_eq!(submenu.slider.unwrap().state, state);
-    assert_eq!(submenu.slider.unwrap().lower, 100);
-    assert_eq!(submenu.slider.unwrap().upper, 150);
+    assert_eq!(slider(&submenu).state, state);
+    assert_eq!(slider(&submenu).lower, 100);
+    assert_eq!(slider(&submenu).upper, 150);
+}
+
+#[test]
+fn submenu_value_summary() {
+    let mut submenu = initialize_submenu(SubMenuType::ToggleSingle);
+    assert_eq!(submenu.value_summary(), "Title");
+    submenu.toggles.get_by_idx_mut(0).unwrap().value = 0;
+    assert_eq!(submenu.value_summary(), "None");
+
+    let mut submenu = initialize_submenu(SubMenuType::ToggleMultiple);
+    assert_eq!(submenu.value_summary(), "3 selected");
+    submenu.toggles.get_by_idx_mut(0).unwrap().value = 1;
+    assert_eq!(submenu.value_summary(), "All");
+    submenu.update_from_vec(vec![0, 1, 0, 1]);
+    assert_eq!(submenu.value_summary(), "Title, Title");
+    submenu.update_from_vec(vec![0, 0, 0, 0]);
+    assert_eq!(submenu.value_summary(), "None");
+
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    assert_eq!(submenu.value_summary(), "0–150");
+    submenu.update_from_vec(vec![40, 40]);
+    assert_eq!(submenu.value_summary(), "40");
+
+    let submenu = initialize_submenu(SubMenuType::Nested);
+    assert_eq!(submenu.value_summary(), "2 settings");
+}
+
+#[test]
+fn submenu_slider_on_l() {
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    let mode = |submenu: &SubMenu| submenu.slider.as_ref().unwrap().mode();
+    assert_eq!(mode(&submenu), SliderMode::Clamp);
+    submenu.on_l();
+    assert_eq!(mode(&submenu), SliderMode::Push);
+    submenu.on_l();
+    assert_eq!(mode(&submenu), SliderMode::LockedWidth);
+    submenu.on_l();
+    assert_eq!(mode(&submenu), SliderMode::Clamp);
+
+    // The mode doesn't change while a handle is selected
+    submenu.on_a();
+    submenu.on_l();
+    assert_eq!(mode(&submenu), SliderMode::Clamp);
+}
+
+#[test]
+fn submenu_slider_button_hints() {
+    let mut app = App::new();
+    let mut submenu = initialize_submenu(SubMenuType::Slider);
+    submenu
+        .slider
+        .as_mut()
+        .unwrap()
+        .get_mut::<u32>()
+        .unwrap()
+        .presets = vec![0, 50, 100];
+    app.tabs = StatefulList::with_items(vec![Tab {
+        title: "Tab",
+        id: "tab",
+        submenus: StatefulTable::with_items(1, 1, vec![submenu]),
+    }]);
+    app.on_a();
+    assert!(app.page == AppPage::SLIDER);
+    let hints = app.button_hints();
+    assert_eq!(hints[0].action, "Select handle");
+    assert!(hints
+        .iter()
+        .any(|hint| hint.button == Button::L && hint.action == "Change mode"));
+
+    // Switching modes through the app shows in the slider's title
+    app.on_l();
+    match app.view().page {
+        PageView::Slider(slider) => assert_eq!(slider.title, "Slider Menu (Push)"),
+        _ => panic!("Expected the slider page"),
+    }
+
+    app.on_a();
+    let actions: Vec<&str> = app.button_hints().iter().map(|hint| hint.action).collect();
+    assert_eq!(
+        actions,
+        vec![
+            "Deselect handle",
+            "Deselect handle",
+            "Type value",
+            "Help",
+            "Increase fast",
+            "Decrease fast",
+            "Decrease",
+            "Increase",
+            "Reset all",
+            "Prev preset",
+            "Next preset"
+        ]
+    );
+
+    // The directions move the selected handle, as hinted
+    let lower = |app: &App| {
+        let slider = app.current_submenu().slider.as_ref().unwrap();
+        slider.get::<u32>().unwrap().lower
+    };
+    let before = lower(&app);
+    app.on_right();
+    assert!(lower(&app) > before);
+
+    app.on_x();
+    assert_eq!(app.button_hints()[0].action, "Confirm value");
 }
@@ -0,0 +1,44 @@
+#![cfg(feature = "has_terminal")]
+
+use training_mod_tui_2::{SymbolSet, Theme, ThemeError, ASCII_SYMBOLS, UNICODE_SYMBOLS};
+
+#[test]
+fn symbol_set_pips() {
+    assert_eq!(UNICODE_SYMBOLS.pips(2, 4), "●●··");
+    assert_eq!(ASCII_SYMBOLS.pips(0, 3), "...");
+    assert_eq!(ASCII_SYMBOLS.pips(3, 3), "###");
+    assert_eq!(ASCII_SYMBOLS.pips(0, 0), "");
+}
+
+#[test]
+fn symbol_set_by_name() {
+    assert_eq!(SymbolSet::by_name("ASCII"), Some(ASCII_SYMBOLS));
+    assert_eq!(SymbolSet::by_name("unicode"), Some(UNICODE_SYMBOLS));
+    assert_eq!(SymbolSet::by_name("emoji"), None);
+    assert_eq!(SymbolSet::default(), UNICODE_SYMBOLS);
+}
+
+#[test]
+fn symbol_set_ascii_fallback_is_ascii() {
+    let s = ASCII_SYMBOLS;
+    for glyph in [
+        s.radio_on,
+        s.radio_off,
+        s.checkbox_on,
+        s.checkbox_off,
+        s.pip_on,
+        s.pip_off,
+    ] {
+        assert!(glyph.is_ascii());
+    }
+}
+
+#[test]
+fn symbol_set_from_theme_file() {
+    let theme = Theme::from_json("{\"symbols\": \"ascii\"}").unwrap();
+    assert_eq!(theme.symbols, ASCII_SYMBOLS);
+    assert!(matches!(
+        Theme::from_json("{\"symbols\": \"emoji\"}"),
+        Err(ThemeError::UnknownSymbols(name)) if name == "emoji"
+    ));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "has_terminal")]
+
+use training_mod_tui_2::visible_tabs;
+
+#[test]
+fn visible_tabs_all_fit() {
+    // Each tab takes its width plus 2 cells of padding, with a 1 cell divider between tabs
+    assert_eq!(visible_tabs(&[5, 5, 5], 0, 23), 0..3);
+    assert_eq!(visible_tabs(&[5, 5, 5], 2, 100), 0..3);
+}
+
+#[test]
+fn visible_tabs_single_tab() {
+    assert_eq!(visible_tabs(&[5], 0, 100), 0..1);
+    assert_eq!(visible_tabs(&[], 0, 100), 0..0);
+}
+
+#[test]
+fn visible_tabs_scrolls_to_selected() {
+    let widths = [5; 6];
+    // Room for exactly two tabs
+    assert_eq!(visible_tabs(&widths, 0, 15), 0..2);
+    assert_eq!(visible_tabs(&widths, 3, 15), 3..5);
+    assert_eq!(visible_tabs(&widths, 5, 15), 4..6);
+    // Room for three tabs keeps the selected one in the middle
+    assert_eq!(visible_tabs(&widths, 3, 23), 2..5);
+}
+
+#[test]
+fn visible_tabs_selected_too_wide() {
+    // The selected tab is always shown, even if it has to be clipped
+    assert_eq!(visible_tabs(&[5, 30, 5], 1, 10), 1..2);
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "has_terminal")]
+
+use ratatui::style::{Color, Modifier, Style};
+use training_mod_tui_2::{Theme, ThemeError, UiState};
+
+#[test]
+fn theme_builtin() {
+    let themes = Theme::builtin();
+    assert_eq!(themes[0], Theme::default());
+    let names: Vec<&str> = themes.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "Default",
+            "High Contrast",
+            "Colour-Blind Safe",
+            "Monochrome"
+        ]
+    );
+}
+
+#[test]
+fn theme_cycle() {
+    let mut ui_state = UiState::default();
+    assert_eq!(ui_state.theme().name, "Default");
+    ui_state.cycle_theme();
+    assert_eq!(ui_state.theme().name, "High Contrast");
+
+    // Cycling wraps back around to the first theme
+    for _ in 0..ui_state.themes.len() - 1 {
+        ui_state.cycle_theme();
+    }
+    assert_eq!(ui_state.theme().name, "Default");
+
+    assert!(ui_state.select_theme("Monochrome"));
+    assert_eq!(ui_state.theme(), &Theme::monochrome());
+    assert!(!ui_state.select_theme("Missing"));
+    assert_eq!(ui_state.theme().name, "Monochrome");
+}
+
+#[test]
+fn theme_add() {
+    let mut ui_state = UiState::default();
+    let count = ui_state.themes.len();
+    let custom = Theme {
+        name: "Custom".to_string(),
+        ..Theme::default()
+    };
+    ui_state.add_theme(custom.clone());
+    assert_eq!(ui_state.themes.len(), count + 1);
+    assert_eq!(ui_state.theme(), &custom);
+
+    // A theme with the same name replaces the existing one
+    let replacement = Theme {
+        name: "Default".to_string(),
+        ..Theme::monochrome()
+    };
+    ui_state.add_theme(replacement.clone());
+    assert_eq!(ui_state.themes.len(), count + 1);
+    assert_eq!(ui_state.selected_theme, 0);
+    assert_eq!(ui_state.theme(), &replacement);
+}
+
+#[test]
+fn theme_monochrome_has_no_colours() {
+    let t = Theme::monochrome();
+    let styles = [
+        t.background,
+        t.text,
+        t.border,
+        t.highlight,
+        t.summary,
+        t.tab,
+        t.tab_selected,
+        t.help,
+        t.message,
+        t.error,
+        t.entry_cursor,
+        t.button,
+        t.slider_track,
+        t.slider_range,
+        t.slider_hover,
+        t.slider_selected,
+        t.slider_label,
+    ];
+    for style in styles {
+        assert_eq!(style.fg, None);
+        assert_eq!(style.bg, None);
+    }
+}
+
+#[test]
+fn theme_from_json() {
+    let json = r##"{
+        "name": "Custom",
+        "base": "high contrast",
+        "highlight": { "fg": "black", "bg": "#5fafff", "modifiers": ["bold", "italic"] },
+        "slider_hover": { "fg": "magenta" }
+    }"##;
+    let t = Theme::from_json(json).unwrap();
+    assert_eq!(t.name, "Custom");
+    assert_eq!(
+        t.highlight,
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Rgb(0x5f, 0xaf, 0xff))
+            .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+    );
+    assert_eq!(t.slider_hover, Style::default().fg(Color::Magenta));
+    // Everything else comes from the base theme
+    assert_eq!(t.slider_selected, Theme::high_contrast().slider_selected);
+
+    let t = Theme::from_json("{\"name\": \"Plain\"}").unwrap();
+    assert_eq!(
+        t,
+        Theme {
+            name: "Plain".to_string(),
+            ..Theme::default()
+        }
+    );
+}
+
+#[test]
+fn theme_from_json_errors() {
+    assert!(matches!(
+        Theme::from_json("not json"),
+        Err(ThemeError::Json(_))
+    ));
+    assert!(matches!(
+        Theme::from_json("[]"),
+        Err(ThemeError::NotAnObject)
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"text\": {\"modifiers\": \"BOLD\"}}"),
+        Err(ThemeError::NotAnArray(key)) if key == "text.modifiers"
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"text\": {\"modifiers\": [1]}}"),
+        Err(ThemeError::NotAString(key)) if key == "text.modifiers"
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"base\": \"Missing\"}"),
+        Err(ThemeError::UnknownBase(name)) if name == "Missing"
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"sparkles\": {}}"),
+        Err(ThemeError::UnknownStyle(key)) if key == "sparkles"
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"text\": {\"fg\": \"not a colour\"}}"),
+        Err(ThemeError::InvalidColor(_))
+    ));
+    assert!(matches!(
+        Theme::from_json("{\"text\": {\"modifiers\": [\"wobbly\"]}}"),
+        Err(ThemeError::UnknownModifier(name)) if name == "wobbly"
+    ));
+    assert!(matches!(
+        Theme::load("/nonexistent/theme.json"),
+        Err(ThemeError::Io(_))
+    ));
+}
@@ -6,6 +6,7 @@ fn toggle_serialize() {
         title: "Title",
         value: 5,
         max: 10,
+        help_text: "",
     };
     let json = serde_json::to_string(&t).unwrap();
     assert_eq!(json, "5");
@@ -17,6 +18,7 @@ fn toggle_increment() {
         title: "Title",
         value: 5,
         max: 10,
+        help_text: "",
     };
     t.increment();
     assert_eq!(t.value, 6);
@@ -33,6 +35,7 @@ fn toggle_decrement() {
         title: "Title",
         value: 5,
         max: 10,
+        help_text: "",
     };
     t.decrement();
     assert_eq!(t.value, 4);